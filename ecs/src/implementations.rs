@@ -1,7 +1,10 @@
 // Part of a very simple ECS for demonstration purposes only.
 
+use std::collections::HashMap;
+
 use morphorm::*;
 use slotmap::SecondaryMap;
+use smallvec::{smallvec, SmallVec};
 
 use crate::entity::Entity;
 use crate::store::Store;
@@ -43,6 +46,10 @@ impl Node for Entity {
         store.wrap.get(*self).copied()
     }
 
+    fn font_size(&self, store: &Store) -> Option<Scalar> {
+        store.font_size.get(*self).copied()
+    }
+
     fn alignment(&self, store: &Store) -> Option<Alignment> {
         store.alignment.get(*self).copied()
     }
@@ -71,13 +78,45 @@ impl Node for Entity {
         store.bottom.get(*self).copied()
     }
 
+    fn min_left(&self, store: &Store) -> Option<Units> {
+        store.min_left.get(*self).copied()
+    }
+
+    fn max_left(&self, store: &Store) -> Option<Units> {
+        store.max_left.get(*self).copied()
+    }
+
+    fn min_right(&self, store: &Store) -> Option<Units> {
+        store.min_right.get(*self).copied()
+    }
+
+    fn max_right(&self, store: &Store) -> Option<Units> {
+        store.max_right.get(*self).copied()
+    }
+
+    fn min_top(&self, store: &Store) -> Option<Units> {
+        store.min_top.get(*self).copied()
+    }
+
+    fn max_top(&self, store: &Store) -> Option<Units> {
+        store.max_top.get(*self).copied()
+    }
+
+    fn min_bottom(&self, store: &Store) -> Option<Units> {
+        store.min_bottom.get(*self).copied()
+    }
+
+    fn max_bottom(&self, store: &Store) -> Option<Units> {
+        store.max_bottom.get(*self).copied()
+    }
+
     fn content_size<'a>(
         &self,
         store: &Store,
         _sublayout: &mut (),
-        width: Option<f32>,
-        height: Option<f32>,
-    ) -> Option<(f32, f32)> {
+        width: Option<Scalar>,
+        height: Option<Scalar>,
+    ) -> Option<(Scalar, Scalar)> {
         store.content_size.get(*self).map(|t| (t)(store, width, height))
     }
 
@@ -105,14 +144,62 @@ impl Node for Entity {
         store.horizontal_gap.get(*self).copied()
     }
 
-    fn vertical_scroll(&self, store: &Store) -> Option<f32> {
+    fn vertical_scroll(&self, store: &Store) -> Option<Scalar> {
         store.vertical_scroll.get(*self).copied()
     }
 
-    fn horizontal_scroll(&self, store: &Store) -> Option<f32> {
+    fn horizontal_scroll(&self, store: &Store) -> Option<Scalar> {
         store.horizontal_scroll.get(*self).copied()
     }
 
+    fn horizontal_overflow(&self, store: &Store) -> Option<Overflow> {
+        store.horizontal_overflow.get(*self).copied()
+    }
+
+    fn vertical_overflow(&self, store: &Store) -> Option<Overflow> {
+        store.vertical_overflow.get(*self).copied()
+    }
+
+    fn clamp_scroll(&self, store: &Store) -> Option<bool> {
+        store.clamp_scroll.get(*self).copied()
+    }
+
+    fn containing_block(&self, store: &Store) -> Option<bool> {
+        store.containing_block.get(*self).copied()
+    }
+
+    fn anchor(&self, store: &Store) -> Option<Entity> {
+        store.anchor.get(*self).copied()
+    }
+
+    fn placement(&self, store: &Store) -> Option<Placement> {
+        store.placement.get(*self).copied()
+    }
+
+    fn z_index(&self, store: &Store) -> Option<i32> {
+        store.z_index.get(*self).copied()
+    }
+
+    fn transform(&self, store: &Store) -> Option<Transform> {
+        store.transform.get(*self).copied()
+    }
+
+    fn focusable(&self, store: &Store) -> Option<bool> {
+        store.focusable.get(*self).copied()
+    }
+
+    fn scale_factor(&self, store: &Store) -> Option<Scalar> {
+        store.scale_factor.get(*self).copied()
+    }
+
+    fn pixel_snapping(&self, store: &Store) -> Option<PixelSnapping> {
+        store.pixel_snapping.get(*self).copied()
+    }
+
+    fn numeric_mode(&self, store: &Store) -> Option<NumericMode> {
+        store.numeric_mode.get(*self).copied()
+    }
+
     fn min_width(&self, store: &Store) -> Option<Units> {
         store.min_width.get(*self).copied()
     }
@@ -188,15 +275,35 @@ impl Node for Entity {
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Rect {
-    pub posx: f32,
-    pub posy: f32,
-    pub width: f32,
-    pub height: f32,
+    pub posx: Scalar,
+    pub posy: Scalar,
+    pub width: Scalar,
+    pub height: Scalar,
 }
 
+// The number of content sizes kept for each node.
+const MAX_MEASUREMENTS: usize = 2;
+
 pub struct NodeCache {
     // Computed size and position of nodes.
     pub rect: SecondaryMap<Entity, Rect>,
+    // Computed width and height of the content of nodes.
+    pub content_size: SecondaryMap<Entity, (Scalar, Scalar)>,
+    // Computed clip rectangle of nodes.
+    pub clip: SecondaryMap<Entity, Rect>,
+    // Computed window-space size and position of nodes.
+    pub window_rect: SecondaryMap<Entity, Rect>,
+    // Computed transform from the coordinate space of nodes to window space.
+    pub transform: SecondaryMap<Entity, Transform>,
+    // Index of nodes in the order in which they should be painted, from back to front, removed when a node is hidden.
+    pub stacking_order: HashMap<Entity, usize>,
+    // Constraints and results of the last layout of nodes, removed when a node is marked dirty.
+    pub layout_record: SecondaryMap<Entity, LayoutRecord>,
+    // Inputs of the passes which last ran after layout for nodes, removed when a node is hidden.
+    pub post_layout_record: SecondaryMap<Entity, PostLayoutRecord>,
+    // The latest content sizes measured for each pair of width and height constraints, removed when a node is marked
+    // dirty.
+    pub measurements: SecondaryMap<Entity, SmallVec<[Measurement; MAX_MEASUREMENTS]>>,
 }
 
 impl NodeCache {
@@ -206,21 +313,60 @@ impl NodeCache {
 
     pub fn remove(&mut self, entity: Entity) {
         self.rect.remove(entity);
+        self.content_size.remove(entity);
+        self.clip.remove(entity);
+        self.window_rect.remove(entity);
+        self.transform.remove(entity);
+        self.stacking_order.remove(&entity);
+        self.layout_record.remove(entity);
+        self.post_layout_record.remove(entity);
+        self.measurements.remove(entity);
     }
 
     pub fn clear(&mut self) {
         self.rect.clear();
+        self.content_size.clear();
+        self.clip.clear();
+        self.window_rect.clear();
+        self.transform.clear();
+        self.stacking_order.clear();
+        self.layout_record.clear();
+        self.post_layout_record.clear();
+        self.measurements.clear();
     }
 
     pub fn bounds(&self, entity: Entity) -> Option<&Rect> {
         self.rect.get(entity)
     }
+
+    pub fn content_size(&self, entity: Entity) -> Option<(Scalar, Scalar)> {
+        self.content_size.get(entity).copied()
+    }
+
+    pub fn clip(&self, entity: Entity) -> Option<&Rect> {
+        self.clip.get(entity)
+    }
+
+    pub fn window_bounds(&self, entity: Entity) -> Option<&Rect> {
+        self.window_rect.get(entity)
+    }
+
+    pub fn transform(&self, entity: Entity) -> Option<Transform> {
+        self.transform.get(entity).copied()
+    }
+
+    /// Returns the visible nodes in the order in which they should be painted, from back to front.
+    pub fn paint_order(&self) -> Vec<Entity> {
+        let mut nodes = self.stacking_order.iter().collect::<Vec<_>>();
+        nodes.sort_by_key(|(_, index)| **index);
+        nodes.into_iter().map(|(entity, _)| *entity).collect()
+    }
 }
 
 impl Cache for NodeCache {
     type Node = Entity;
 
-    fn set_bounds(&mut self, node: &Self::Node, posx: f32, posy: f32, width: f32, height: f32) {
+    fn set_bounds(&mut self, node: &Self::Node, posx: Scalar, posy: Scalar, width: Scalar, height: Scalar) {
         if let Some(rect) = self.rect.get_mut(*node) {
             rect.posx = posx;
             rect.posy = posy;
@@ -229,7 +375,76 @@ impl Cache for NodeCache {
         }
     }
 
-    fn width(&self, node: &Self::Node) -> f32 {
+    fn set_content_size(&mut self, node: &Self::Node, width: Scalar, height: Scalar) {
+        self.content_size.insert(*node, (width, height));
+    }
+
+    fn set_clip(&mut self, node: &Self::Node, posx: Scalar, posy: Scalar, width: Scalar, height: Scalar) {
+        self.clip.insert(*node, Rect { posx, posy, width, height });
+    }
+
+    fn clip(&self, node: &Self::Node) -> Option<(Scalar, Scalar, Scalar, Scalar)> {
+        self.clip.get(*node).map(|rect| (rect.posx, rect.posy, rect.width, rect.height))
+    }
+
+    fn set_window_bounds(&mut self, node: &Self::Node, posx: Scalar, posy: Scalar, width: Scalar, height: Scalar) {
+        self.window_rect.insert(*node, Rect { posx, posy, width, height });
+    }
+
+    fn set_transform(&mut self, node: &Self::Node, transform: Transform) {
+        self.transform.insert(*node, transform);
+    }
+
+    fn set_stacking_order(&mut self, node: &Self::Node, index: usize) {
+        self.stacking_order.insert(*node, index);
+    }
+
+    fn layout_record(&self, node: &Self::Node) -> Option<LayoutRecord> {
+        self.layout_record.get(*node).copied()
+    }
+
+    fn set_layout_record(&mut self, node: &Self::Node, record: LayoutRecord) {
+        self.layout_record.insert(*node, record);
+    }
+
+    fn post_layout_record(&self, node: &Self::Node) -> Option<PostLayoutRecord> {
+        self.post_layout_record.get(*node).copied()
+    }
+
+    fn set_post_layout_record(&mut self, node: &Self::Node, record: PostLayoutRecord) {
+        self.post_layout_record.insert(*node, record);
+    }
+
+    fn content_measurement(
+        &self,
+        node: &Self::Node,
+        width: Option<Scalar>,
+        height: Option<Scalar>,
+    ) -> Option<(Scalar, Scalar)> {
+        find_measurement(self.measurements.get(*node)?, width, height)
+    }
+
+    fn set_content_measurement(
+        &mut self,
+        node: &Self::Node,
+        width: Option<Scalar>,
+        height: Option<Scalar>,
+        size: (Scalar, Scalar),
+    ) {
+        let measurement = (width, height, size);
+        if let Some(measurements) = self.measurements.get_mut(*node) {
+            // The oldest measurement is replaced, so a node which is measured with changing constraints, such as text
+            // in a window being resized, does not keep a measurement for every constraint.
+            if measurements.len() == MAX_MEASUREMENTS {
+                measurements.remove(0);
+            }
+            measurements.push(measurement);
+        } else {
+            self.measurements.insert(*node, smallvec![measurement]);
+        }
+    }
+
+    fn width(&self, node: &Self::Node) -> Scalar {
         if let Some(rect) = self.rect.get(*node) {
             return rect.width;
         }
@@ -237,7 +452,7 @@ impl Cache for NodeCache {
         0.0
     }
 
-    fn height(&self, node: &Self::Node) -> f32 {
+    fn height(&self, node: &Self::Node) -> Scalar {
         if let Some(rect) = self.rect.get(*node) {
             return rect.height;
         }
@@ -245,7 +460,7 @@ impl Cache for NodeCache {
         0.0
     }
 
-    fn posx(&self, node: &Self::Node) -> f32 {
+    fn posx(&self, node: &Self::Node) -> Scalar {
         if let Some(rect) = self.rect.get(*node) {
             return rect.posx;
         }
@@ -253,7 +468,7 @@ impl Cache for NodeCache {
         0.0
     }
 
-    fn posy(&self, node: &Self::Node) -> f32 {
+    fn posy(&self, node: &Self::Node) -> Scalar {
         if let Some(rect) = self.rect.get(*node) {
             return rect.posy;
         }
@@ -264,6 +479,16 @@ impl Cache for NodeCache {
 
 impl Default for NodeCache {
     fn default() -> Self {
-        Self { rect: SecondaryMap::new() }
+        Self {
+            rect: SecondaryMap::new(),
+            content_size: SecondaryMap::new(),
+            clip: SecondaryMap::new(),
+            window_rect: SecondaryMap::new(),
+            transform: SecondaryMap::new(),
+            stacking_order: HashMap::new(),
+            layout_record: SecondaryMap::new(),
+            post_layout_record: SecondaryMap::new(),
+            measurements: SecondaryMap::new(),
+        }
     }
 }
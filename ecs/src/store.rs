@@ -1,10 +1,13 @@
 // Part of a very simple ECS for demonstration purposes only.
 
 use crate::{entity::Entity, TextWrap};
-use morphorm::{Alignment, Direction, LayoutType, LayoutWrap, PositionType, Units};
+use morphorm::{
+    Alignment, Direction, LayoutType, LayoutWrap, NumericMode, Overflow, PixelSnapping, Placement, PositionType,
+    Scalar, Transform, Units,
+};
 use slotmap::SecondaryMap;
 
-type ContentSizeType<S> = Box<dyn Fn(&S, Option<f32>, Option<f32>) -> (f32, f32)>;
+type ContentSizeType<S> = Box<dyn Fn(&S, Option<Scalar>, Option<Scalar>) -> (Scalar, Scalar)>;
 
 /// A storage struct representing a component store for an ECS.
 #[derive(Default)]
@@ -16,6 +19,7 @@ pub struct Store {
     pub direction: SecondaryMap<Entity, Direction>,
     pub alignment: SecondaryMap<Entity, Alignment>,
     pub wrap: SecondaryMap<Entity, LayoutWrap>,
+    pub font_size: SecondaryMap<Entity, Scalar>,
 
     pub grid_columns: SecondaryMap<Entity, Vec<Units>>,
     pub grid_rows: SecondaryMap<Entity, Vec<Units>>,
@@ -24,13 +28,33 @@ pub struct Store {
     pub column_span: SecondaryMap<Entity, usize>,
     pub row_span: SecondaryMap<Entity, usize>,
 
-    pub vertical_scroll: SecondaryMap<Entity, f32>,
-    pub horizontal_scroll: SecondaryMap<Entity, f32>,
+    pub vertical_scroll: SecondaryMap<Entity, Scalar>,
+    pub horizontal_scroll: SecondaryMap<Entity, Scalar>,
+    pub clamp_scroll: SecondaryMap<Entity, bool>,
+    pub horizontal_overflow: SecondaryMap<Entity, Overflow>,
+    pub vertical_overflow: SecondaryMap<Entity, Overflow>,
+    pub containing_block: SecondaryMap<Entity, bool>,
+    pub anchor: SecondaryMap<Entity, Entity>,
+    pub placement: SecondaryMap<Entity, Placement>,
+    pub z_index: SecondaryMap<Entity, i32>,
+    pub transform: SecondaryMap<Entity, Transform>,
+    pub focusable: SecondaryMap<Entity, bool>,
+    pub scale_factor: SecondaryMap<Entity, Scalar>,
+    pub pixel_snapping: SecondaryMap<Entity, PixelSnapping>,
+    pub numeric_mode: SecondaryMap<Entity, NumericMode>,
 
     pub left: SecondaryMap<Entity, Units>,
     pub right: SecondaryMap<Entity, Units>,
     pub top: SecondaryMap<Entity, Units>,
     pub bottom: SecondaryMap<Entity, Units>,
+    pub min_left: SecondaryMap<Entity, Units>,
+    pub max_left: SecondaryMap<Entity, Units>,
+    pub min_right: SecondaryMap<Entity, Units>,
+    pub max_right: SecondaryMap<Entity, Units>,
+    pub min_top: SecondaryMap<Entity, Units>,
+    pub max_top: SecondaryMap<Entity, Units>,
+    pub min_bottom: SecondaryMap<Entity, Units>,
+    pub max_bottom: SecondaryMap<Entity, Units>,
 
     pub width: SecondaryMap<Entity, Units>,
     pub height: SecondaryMap<Entity, Units>,
@@ -76,12 +100,33 @@ impl Store {
         self.position_type.remove(entity);
         self.direction.remove(entity);
         self.wrap.remove(entity);
+        self.font_size.remove(entity);
         self.left.remove(entity);
         self.right.remove(entity);
         self.top.remove(entity);
         self.bottom.remove(entity);
+        self.min_left.remove(entity);
+        self.max_left.remove(entity);
+        self.min_right.remove(entity);
+        self.max_right.remove(entity);
+        self.min_top.remove(entity);
+        self.max_top.remove(entity);
+        self.min_bottom.remove(entity);
+        self.max_bottom.remove(entity);
         self.vertical_scroll.remove(entity);
         self.horizontal_scroll.remove(entity);
+        self.clamp_scroll.remove(entity);
+        self.horizontal_overflow.remove(entity);
+        self.vertical_overflow.remove(entity);
+        self.containing_block.remove(entity);
+        self.anchor.remove(entity);
+        self.placement.remove(entity);
+        self.z_index.remove(entity);
+        self.transform.remove(entity);
+        self.focusable.remove(entity);
+        self.scale_factor.remove(entity);
+        self.pixel_snapping.remove(entity);
+        self.numeric_mode.remove(entity);
         self.width.remove(entity);
         self.height.remove(entity);
         self.min_width.remove(entity);
@@ -116,12 +161,33 @@ impl Store {
         self.position_type.clear();
         self.direction.clear();
         self.wrap.clear();
+        self.font_size.clear();
         self.left.clear();
         self.right.clear();
         self.top.clear();
         self.bottom.clear();
+        self.min_left.clear();
+        self.max_left.clear();
+        self.min_right.clear();
+        self.max_right.clear();
+        self.min_top.clear();
+        self.max_top.clear();
+        self.min_bottom.clear();
+        self.max_bottom.clear();
         self.vertical_scroll.clear();
         self.horizontal_scroll.clear();
+        self.clamp_scroll.clear();
+        self.horizontal_overflow.clear();
+        self.vertical_overflow.clear();
+        self.containing_block.clear();
+        self.anchor.clear();
+        self.placement.clear();
+        self.z_index.clear();
+        self.transform.clear();
+        self.focusable.clear();
+        self.scale_factor.clear();
+        self.pixel_snapping.clear();
+        self.numeric_mode.clear();
         self.width.clear();
         self.height.clear();
         self.min_width.clear();
@@ -1,11 +1,14 @@
 // Part of a very simple ECS for demonstration purposes only.
 
-use morphorm::{Alignment, Direction, LayoutType, LayoutWrap, PositionType, Units};
+use morphorm::{
+    Alignment, Direction, LayoutType, LayoutWrap, NumericMode, Overflow, PixelSnapping, Placement, PositionType,
+    Scalar, Transform, Units,
+};
 
 use crate::entity::{Entity, EntityManager};
 use crate::implementations::NodeCache;
 use crate::store::Store;
-use crate::tree::Tree;
+use crate::tree::{ChildIterator, Tree};
 
 use rand::Rng;
 
@@ -34,6 +37,10 @@ impl World {
         self.tree.add(entity, parent);
         self.cache.add(entity);
 
+        if let Some(parent) = parent {
+            self.mark_dirty(parent);
+        }
+
         let random_red: u8 = rand::thread_rng().gen();
         let random_green: u8 = rand::thread_rng().gen();
         let random_blue: u8 = rand::thread_rng().gen();
@@ -46,11 +53,36 @@ impl World {
 
     /// Remove a node from the world.
     pub fn remove(&mut self, entity: Entity) {
+        self.mark_dirty(entity);
+        self.forget_paint_order(entity);
         self.store.remove(entity);
         self.cache.remove(entity);
         self.tree.remove(&entity);
     }
 
+    /// Mark the given entity and its ancestors as needing layout, so that the results of their previous layout are not
+    /// reused. Content sizes measured for these entities are also discarded.
+    /// This is done automatically when a property of the entity is set or its children change.
+    pub fn mark_dirty(&mut self, entity: Entity) {
+        let mut current = Some(entity);
+        while let Some(entity) = current {
+            self.cache.layout_record.remove(entity);
+            self.cache.measurements.remove(entity);
+            current = self.tree.parent.get(entity.index()).copied().flatten();
+        }
+    }
+
+    /// Remove the given entity and its descendants from the paint order, along with the records of the passes which ran
+    /// after their last layout, once they are no longer painted.
+    fn forget_paint_order(&mut self, entity: Entity) {
+        let mut stack = vec![entity];
+        while let Some(entity) = stack.pop() {
+            self.cache.stacking_order.remove(&entity);
+            self.cache.post_layout_record.remove(entity);
+            stack.extend(ChildIterator { tree: &self.tree, current_node: self.tree.get_first_child(&entity) });
+        }
+    }
+
     pub fn clear(&mut self) {
         self.entity_manager.reset();
         self.store.clear();
@@ -61,74 +93,143 @@ impl World {
     /// Set the desired layout type of the given entity.
     pub fn set_layout_type(&mut self, entity: Entity, value: LayoutType) {
         self.store.layout_type.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired position type of the given entity.
     pub fn set_position_type(&mut self, entity: Entity, value: PositionType) {
         self.store.position_type.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the inline direction used for horizontal positioning semantics for the given entity.
     pub fn set_direction(&mut self, entity: Entity, value: Direction) {
         self.store.direction.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the wrap mode for children of the given entity.
     pub fn set_wrap(&mut self, entity: Entity, value: LayoutWrap) {
         self.store.wrap.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the font size used to resolve font-relative units for the given entity and its descendants.
+    pub fn set_font_size(&mut self, entity: Entity, value: Scalar) {
+        self.store.font_size.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     pub fn set_alignment(&mut self, entity: Entity, value: Alignment) {
         self.store.alignment.insert(entity, value);
+        self.mark_dirty(entity);
     }
     /// Set the desired width of the given entity.
     pub fn set_width(&mut self, entity: Entity, value: Units) {
         self.store.width.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the minimum width of the given entity.
     pub fn set_min_width(&mut self, entity: Entity, value: Units) {
         self.store.min_width.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the maximum width of the given entity.
     pub fn set_max_width(&mut self, entity: Entity, value: Units) {
         self.store.max_width.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the minimum height of the given entity.
     pub fn set_min_height(&mut self, entity: Entity, value: Units) {
         self.store.min_height.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the maximum height of the given entity.
     pub fn set_max_height(&mut self, entity: Entity, value: Units) {
         self.store.max_height.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired height of the given entity.
     pub fn set_height(&mut self, entity: Entity, value: Units) {
         self.store.height.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired left space of the given entity.
     pub fn set_left(&mut self, entity: Entity, value: Units) {
         self.store.left.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired right space of the given entity.
     pub fn set_right(&mut self, entity: Entity, value: Units) {
         self.store.right.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired top space of the given entity.
     pub fn set_top(&mut self, entity: Entity, value: Units) {
         self.store.top.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired bottom space of the given entity.
     pub fn set_bottom(&mut self, entity: Entity, value: Units) {
         self.store.bottom.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the minimum left space of the given entity.
+    pub fn set_min_left(&mut self, entity: Entity, value: Units) {
+        self.store.min_left.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the maximum left space of the given entity.
+    pub fn set_max_left(&mut self, entity: Entity, value: Units) {
+        self.store.max_left.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the minimum right space of the given entity.
+    pub fn set_min_right(&mut self, entity: Entity, value: Units) {
+        self.store.min_right.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the maximum right space of the given entity.
+    pub fn set_max_right(&mut self, entity: Entity, value: Units) {
+        self.store.max_right.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the minimum top space of the given entity.
+    pub fn set_min_top(&mut self, entity: Entity, value: Units) {
+        self.store.min_top.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the maximum top space of the given entity.
+    pub fn set_max_top(&mut self, entity: Entity, value: Units) {
+        self.store.max_top.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the minimum bottom space of the given entity.
+    pub fn set_min_bottom(&mut self, entity: Entity, value: Units) {
+        self.store.min_bottom.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the maximum bottom space of the given entity.
+    pub fn set_max_bottom(&mut self, entity: Entity, value: Units) {
+        self.store.max_bottom.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired padding of the given entity.
@@ -137,113 +238,219 @@ impl World {
         self.store.padding_right.insert(entity, value);
         self.store.padding_top.insert(entity, value);
         self.store.padding_bottom.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired padding_left space of the given entity.
     pub fn set_padding_left(&mut self, entity: Entity, value: Units) {
         self.store.padding_left.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired padding_right space of the given entity.
     pub fn set_padding_right(&mut self, entity: Entity, value: Units) {
         self.store.padding_right.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired padding_top space of the given entity.
     pub fn set_padding_top(&mut self, entity: Entity, value: Units) {
         self.store.padding_top.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired padding_bottom space of the given entity.
     pub fn set_padding_bottom(&mut self, entity: Entity, value: Units) {
         self.store.padding_bottom.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired vertical (row) space between children of the given entity.
     pub fn set_vertical_gap(&mut self, entity: Entity, value: Units) {
         self.store.vertical_gap.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired horizontal (column) space between children of the given entity.
     pub fn set_horizontal_gap(&mut self, entity: Entity, value: Units) {
         self.store.horizontal_gap.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired minimum vertical (row) space between children of the given entity.
     pub fn set_min_vertical_gap(&mut self, entity: Entity, value: Units) {
         self.store.min_vertical_gap.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired minimum horizontal (column) space between children of the given entity.
     pub fn set_min_horizontal_gap(&mut self, entity: Entity, value: Units) {
         self.store.min_horizontal_gap.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired maximum vertical (row) space between children of the given entity.
     pub fn set_max_vertical_gap(&mut self, entity: Entity, value: Units) {
         self.store.max_vertical_gap.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired maximum horizontal (column) space between children of the given entity.
     pub fn set_max_horizontal_gap(&mut self, entity: Entity, value: Units) {
         self.store.max_horizontal_gap.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired vertical scroll offset.
-    pub fn set_vertical_scroll(&mut self, entity: Entity, value: f32) {
+    pub fn set_vertical_scroll(&mut self, entity: Entity, value: Scalar) {
         self.store.vertical_scroll.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the desired horizontal scroll offset.
-    pub fn set_horizontal_scroll(&mut self, entity: Entity, value: f32) {
+    pub fn set_horizontal_scroll(&mut self, entity: Entity, value: Scalar) {
         self.store.horizontal_scroll.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set whether the scroll offsets of the given entity are clamped to the scrollable range.
+    pub fn set_clamp_scroll(&mut self, entity: Entity, value: bool) {
+        self.store.clamp_scroll.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the overflow mode of the given entity on both axes.
+    pub fn set_overflow(&mut self, entity: Entity, value: Overflow) {
+        self.store.horizontal_overflow.insert(entity, value);
+        self.store.vertical_overflow.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the horizontal overflow mode of the given entity.
+    pub fn set_horizontal_overflow(&mut self, entity: Entity, value: Overflow) {
+        self.store.horizontal_overflow.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the vertical overflow mode of the given entity.
+    pub fn set_vertical_overflow(&mut self, entity: Entity, value: Overflow) {
+        self.store.vertical_overflow.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set whether the given entity is a containing block for its absolute descendants.
+    pub fn set_containing_block(&mut self, entity: Entity, value: bool) {
+        self.store.containing_block.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the node which the given absolute entity is placed next to.
+    pub fn set_anchor(&mut self, entity: Entity, anchor: Entity) {
+        self.store.anchor.insert(entity, anchor);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the placement of the given entity relative to its anchor.
+    pub fn set_placement(&mut self, entity: Entity, value: Placement) {
+        self.store.placement.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the z-index of the given entity.
+    pub fn set_z_index(&mut self, entity: Entity, value: i32) {
+        self.store.z_index.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the transform of the given entity.
+    pub fn set_transform(&mut self, entity: Entity, value: Transform) {
+        self.store.transform.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set whether the given entity can receive focus during spatial navigation.
+    pub fn set_focusable(&mut self, entity: Entity, value: bool) {
+        self.store.focusable.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the number of device pixels per layout pixel of the given root entity.
+    pub fn set_scale_factor(&mut self, entity: Entity, value: Scalar) {
+        self.store.scale_factor.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the pixel snapping policy of the given root entity.
+    pub fn set_pixel_snapping(&mut self, entity: Entity, value: PixelSnapping) {
+        self.store.pixel_snapping.insert(entity, value);
+        self.mark_dirty(entity);
+    }
+
+    /// Set the numeric mode of the given root entity.
+    pub fn set_numeric_mode(&mut self, entity: Entity, value: NumericMode) {
+        self.store.numeric_mode.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     pub fn set_grid_columns(&mut self, entity: Entity, value: Vec<Units>) {
         self.store.grid_columns.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     pub fn set_grid_rows(&mut self, entity: Entity, value: Vec<Units>) {
         self.store.grid_rows.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     pub fn set_column_start(&mut self, entity: Entity, value: usize) {
         self.store.column_start.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     pub fn set_row_start(&mut self, entity: Entity, value: usize) {
         self.store.row_start.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     pub fn set_column_span(&mut self, entity: Entity, value: usize) {
         self.store.column_span.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     pub fn set_row_span(&mut self, entity: Entity, value: usize) {
         self.store.row_span.insert(entity, value);
+        self.mark_dirty(entity);
     }
 
     /// Set the content size function for the given entity.
     pub fn set_content_size(
         &mut self,
         entity: Entity,
-        content: impl Fn(&Store, Option<f32>, Option<f32>) -> (f32, f32) + 'static,
+        content: impl Fn(&Store, Option<Scalar>, Option<Scalar>) -> (Scalar, Scalar) + 'static,
     ) {
         self.store.content_size.insert(entity, Box::new(content));
+        self.mark_dirty(entity);
     }
 
     pub fn set_visibility(&mut self, entity: Entity, visible: bool) {
         self.store.visible.insert(entity, visible);
+        self.mark_dirty(entity);
+
+        if !visible {
+            self.forget_paint_order(entity);
+        }
     }
 
     /// Set the text to be displayed on the given entity.
     pub fn set_text(&mut self, entity: Entity, text: &str) {
         self.store.text.insert(entity, String::from(text));
+        self.mark_dirty(entity);
     }
 
     /// Set whether the text should wrap for the given entity.
     pub fn set_text_wrap(&mut self, entity: Entity, text_wrap: TextWrap) {
         self.store.text_wrap.insert(entity, text_wrap);
+        self.mark_dirty(entity);
     }
 
     /// Set all space and size properties of the given node to stretch.
@@ -261,5 +468,6 @@ impl World {
         self.store.border_right.insert(entity, width);
         self.store.border_top.insert(entity, width);
         self.store.border_bottom.insert(entity, width);
+        self.mark_dirty(entity);
     }
 }
@@ -1,4 +1,9 @@
-use crate::{LayoutType, Node};
+use std::collections::HashMap;
+
+use smallvec::SmallVec;
+
+use crate::layout::{PassInput, PassRecord, Summary, PASSES, WINDOW_PASS};
+use crate::{LayoutType, Node, Scalar, Size, Transform, UnitContext};
 
 /// The `Cache` is a store which contains the computed size and position of nodes
 /// after a layout calculation.
@@ -10,16 +15,467 @@ pub trait Cache {
     /// A type which represents a layout node and implements the [`Node`] trait.
     type Node: Node;
     /// Returns the cached width of the given node.
-    fn width(&self, node: &Self::Node) -> f32;
+    fn width(&self, node: &Self::Node) -> Scalar;
     /// Returns the cached height of the given node.
-    fn height(&self, node: &Self::Node) -> f32;
+    fn height(&self, node: &Self::Node) -> Scalar;
     /// Returns the cached horizontal position of the given node.
-    fn posx(&self, node: &Self::Node) -> f32;
+    fn posx(&self, node: &Self::Node) -> Scalar;
     /// Returns the cached vertical position of the given node.
-    fn posy(&self, node: &Self::Node) -> f32;
+    fn posy(&self, node: &Self::Node) -> Scalar;
 
     /// Sets the cached position and size of the given node.
-    fn set_bounds(&mut self, node: &Self::Node, posx: f32, posy: f32, width: f32, height: f32);
+    fn set_bounds(&mut self, node: &Self::Node, posx: Scalar, posy: Scalar, width: Scalar, height: Scalar);
+
+    /// Sets the size of the content of the given node, which is the union of its padding box and the bounds of its
+    /// relative children before scrolling, extended by its padding.
+    ///
+    /// The maximum scroll offset on each axis is the content size minus the size of the padding box.
+    /// Does nothing by default.
+    fn set_content_size(&mut self, _node: &Self::Node, _width: Scalar, _height: Scalar) {}
+
+    /// Sets the clip rectangle of the given node, which is the part of its bounds that remains visible after
+    /// clipping by the overflow of its ancestors. The rectangle is in the same coordinate space as the bounds.
+    /// Does nothing by default.
+    fn set_clip(&mut self, _node: &Self::Node, _posx: Scalar, _posy: Scalar, _width: Scalar, _height: Scalar) {}
+
+    /// Returns the clip rectangle of the given node as `(posx, posy, width, height)`, as set by
+    /// [`set_clip`](Cache::set_clip), which is used to exclude the clipped parts of nodes from hit testing.
+    /// Returns `None` by default, so nodes are not clipped when hit testing.
+    fn clip(&self, _node: &Self::Node) -> Option<(Scalar, Scalar, Scalar, Scalar)> {
+        None
+    }
+
+    /// Sets the index of the given node in the order in which nodes should be painted, from back to front, as
+    /// determined by the [`z_index`](crate::Node::z_index) of the nodes. Hit testing should use the reverse order.
+    ///
+    /// If the cache stores [post-layout records](Cache::post_layout_record), the indices of the nodes in a subtree
+    /// which is skipped by the passes after layout are not reported again if they are unchanged, so the cache must keep
+    /// the index of each node until it changes or the node is hidden or removed.
+    /// Does nothing by default.
+    fn set_stacking_order(&mut self, _node: &Self::Node, _index: usize) {}
+
+    /// Sets the window-space bounds of the given node, which are its bounds with the positions of its ancestors
+    /// accumulated, including their scroll offsets. The position of the root node is its position in the window.
+    /// For a transformed node, these are the bounding box of its transformed bounds.
+    /// Does nothing by default.
+    fn set_window_bounds(&mut self, _node: &Self::Node, _posx: Scalar, _posy: Scalar, _width: Scalar, _height: Scalar) {
+    }
+
+    /// Sets the transform from the coordinate space of the given node to window space, including the
+    /// [`transform`](crate::Node::transform) of the node and its ancestors.
+    /// Does nothing by default.
+    fn set_transform(&mut self, _node: &Self::Node, _transform: Transform) {}
+
+    /// Returns the record of the last layout of the given node, or `None` if the node must be laid out again.
+    ///
+    /// Together with [`set_layout_record`](Cache::set_layout_record), this enables incremental layout, in which the
+    /// layout of a node and its descendants is skipped if the node was last laid out with the same constraints. The
+    /// record of a node, and the records of all of its ancestors, must be removed whenever a layout property, the
+    /// content, or the children of the node change.
+    ///
+    /// The descendants of a skipped node keep their bounds from the previous layout. The passes which run after layout,
+    /// such as sticky positioning, pixel snapping and clipping, are also skipped for them if the cache stores
+    /// [post-layout records](Cache::post_layout_record).
+    /// Returns `None` by default, so every node is laid out.
+    fn layout_record(&self, _node: &Self::Node) -> Option<LayoutRecord> {
+        None
+    }
+
+    /// Stores the record of the last layout of the given node.
+    /// Does nothing by default.
+    fn set_layout_record(&mut self, _node: &Self::Node, _record: LayoutRecord) {}
+
+    /// Returns the record of the passes which last ran after layout for the given node, or `None` if there is none.
+    ///
+    /// Together with [`set_post_layout_record`](Cache::set_post_layout_record) and
+    /// [`layout_record`](Cache::layout_record), this allows the passes after layout to skip the descendants of a node
+    /// whose layout was skipped, as long as the passes reach the node with the same inputs as last time. The record of a
+    /// node must be removed when the node is hidden or removed, along with the records of its descendants.
+    /// Returns `None` by default, so the passes after layout visit every visible node.
+    fn post_layout_record(&self, _node: &Self::Node) -> Option<PostLayoutRecord> {
+        None
+    }
+
+    /// Stores the record of the passes which last ran after layout for the given node.
+    /// Does nothing by default.
+    fn set_post_layout_record(&mut self, _node: &Self::Node, _record: PostLayoutRecord) {}
+
+    /// Returns the [content size](crate::Node::content_size) previously measured for the given node with the given
+    /// width and height constraints, or `None` if the content must be measured.
+    ///
+    /// Content sizes are always reused within a single layout. Together with
+    /// [`set_content_measurement`](Cache::set_content_measurement), this allows them to be reused across layouts as
+    /// well, in which case the measurements of a node must be removed whenever its content or layout properties
+    /// change, such as when the node is marked dirty for incremental layout.
+    /// Returns `None` by default.
+    fn content_measurement(
+        &self,
+        _node: &Self::Node,
+        _width: Option<Scalar>,
+        _height: Option<Scalar>,
+    ) -> Option<(Scalar, Scalar)> {
+        None
+    }
+
+    /// Stores the content size measured for the given node with the given width and height constraints.
+    /// Does nothing by default.
+    fn set_content_measurement(
+        &mut self,
+        _node: &Self::Node,
+        _width: Option<Scalar>,
+        _height: Option<Scalar>,
+        _size: (Scalar, Scalar),
+    ) {
+    }
+}
+
+/// A [content size](crate::Node::content_size) measured for a node, along with the width and height constraints it
+/// was measured with.
+pub type Measurement = (Option<Scalar>, Option<Scalar>, (Scalar, Scalar));
+
+/// Returns the content size of the measurement taken with the given width and height constraints, if any.
+///
+/// Constraints are compared by their bits, so that a measurement is only reused for exactly the same constraints.
+pub fn find_measurement(
+    measurements: &[Measurement],
+    width: Option<Scalar>,
+    height: Option<Scalar>,
+) -> Option<(Scalar, Scalar)> {
+    let same = |a: Option<Scalar>, b: Option<Scalar>| a.map(Scalar::to_bits) == b.map(Scalar::to_bits);
+
+    measurements.iter().find(|(w, h, _)| same(*w, width) && same(*h, height)).map(|(_, _, size)| *size)
+}
+
+/// Whether the passes after layout visit the descendants of a node.
+#[derive(Debug, Clone)]
+enum VisitState {
+    /// The descendants are visited.
+    Visited,
+    /// The descendants are skipped for as long as the passes are given the same inputs as in the given record.
+    Skipped(Box<PostLayoutRecord>),
+}
+
+/// The state of a node which is only kept for the duration of a layout.
+#[derive(Default)]
+struct NodeState {
+    /// Content sizes measured during the layout which the wrapped cache does not store.
+    measurements: SmallVec<[Measurement; 2]>,
+    /// The borders resolved by the layout of the node.
+    border: Option<[Scalar; 4]>,
+    /// Whether the node was laid out, or restored from its layout record, rather than skipped.
+    laid_out: bool,
+    /// Whether the passes after layout visit the descendants of the node, once the first of them reaches it.
+    visit: Option<VisitState>,
+    /// The inputs of the passes after layout which have reached the node so far.
+    post: PostLayoutRecord,
+}
+
+/// Wraps the cache passed to [`layout`](crate::Node::layout) to reuse the content sizes measured for each node during
+/// the layout, in addition to any measurements stored by the wrapped cache.
+///
+/// The state of each node is kept in a single map, which is only allocated once the first node is laid out.
+pub(crate) struct LayoutCache<'c, C: Cache> {
+    cache: &'c mut C,
+    nodes: HashMap<<C::Node as Node>::CacheKey, NodeState>,
+}
+
+impl<'c, C: Cache> LayoutCache<'c, C> {
+    pub(crate) fn new(cache: &'c mut C) -> Self {
+        Self { cache, nodes: HashMap::new() }
+    }
+
+    /// Returns the state of the given node, creating it if necessary.
+    fn state(&mut self, node: &C::Node) -> &mut NodeState {
+        self.nodes.entry(node.key()).or_default()
+    }
+}
+
+/// Helper trait for sharing the results of the layout of a node with the passes which run after layout.
+pub(crate) trait LayoutState: Cache {
+    /// Returns the widths of the left, top, right and bottom borders of the given node, as resolved by its layout.
+    fn border(&self, node: &Self::Node) -> Option<[Scalar; 4]>;
+
+    /// Stores the widths of the left, top, right and bottom borders of the given node.
+    fn set_border(&mut self, node: &Self::Node, border: [Scalar; 4]);
+
+    /// Marks the given node as laid out, so that the passes after layout visit its descendants.
+    fn set_laid_out(&mut self, node: &Self::Node);
+
+    /// Records that a pass after layout has reached the given node with the given input, and returns whether the pass
+    /// should visit its descendants.
+    fn visit(&mut self, node: &Self::Node, input: PassInput) -> Visit;
+
+    /// Returns the record of the previous passes after layout for the given node if its descendants are skipped.
+    fn skipped(&self, node: &Self::Node) -> Option<PostLayoutRecord>;
+
+    /// Stops skipping the descendants of the given node, returning the inputs of the passes which have reached it so
+    /// far, which must be run again for its descendants.
+    fn unskip(&mut self, node: &Self::Node) -> [Option<PassRecord>; PASSES];
+
+    /// Stores the summary of the subtree of the given node.
+    fn set_summary(&mut self, node: &Self::Node, summary: Summary);
+
+    /// Stores the index of the given node in the paint order.
+    fn set_paint_index(&mut self, node: &Self::Node, index: usize);
+}
+
+/// Whether a pass after layout should visit the descendants of a node.
+pub(crate) enum Visit {
+    /// The descendants are left as they were after the previous layout.
+    Skip,
+    /// The descendants are visited.
+    Descend,
+    /// The descendants must be restored from their layout records and given the passes with the given inputs, which
+    /// have already run for the node, before they are visited.
+    Restore(Box<[Option<PassRecord>; PASSES]>),
+}
+
+impl<C: Cache> LayoutState for LayoutCache<'_, C> {
+    fn border(&self, node: &Self::Node) -> Option<[Scalar; 4]> {
+        self.nodes.get(&node.key()).and_then(|state| state.border)
+    }
+
+    fn set_border(&mut self, node: &Self::Node, border: [Scalar; 4]) {
+        self.state(node).border = Some(border);
+    }
+
+    fn set_laid_out(&mut self, node: &Self::Node) {
+        self.state(node).laid_out = true;
+    }
+
+    fn visit(&mut self, node: &Self::Node, input: PassInput) -> Visit {
+        let bounds = [self.cache.posx(node), self.cache.posy(node), self.cache.width(node), self.cache.height(node)];
+        let last = self.cache.post_layout_record(node);
+
+        let state = self.nodes.entry(node.key()).or_default();
+        let slot = input.index(&state.post.passes);
+        state.post.passes[slot] = Some(PassRecord { input, bounds });
+
+        // The passes which have already run for the node, and must be run again for its restored descendants.
+        let mut before = state.post.passes;
+        before[slot..].fill(None);
+
+        let visit = match &state.visit {
+            Some(VisitState::Visited) => Visit::Descend,
+
+            // Only a node whose layout was skipped can skip its descendants, if the first pass reaches it with the same
+            // input as last time. Anchored nodes may be moved next to anchors outside of the subtree.
+            None if !state.laid_out => match last {
+                Some(last)
+                    if slot == 0
+                        && last.summary.is_some_and(|summary| !summary.anchored)
+                        && last.passes[0] == state.post.passes[0] =>
+                {
+                    state.visit = Some(VisitState::Skipped(Box::new(last)));
+                    Visit::Skip
+                }
+                _ => {
+                    state.visit = Some(VisitState::Visited);
+                    Visit::Restore(Box::new(before))
+                }
+            },
+
+            None => {
+                state.visit = Some(VisitState::Visited);
+                Visit::Descend
+            }
+
+            // The last pass also checks that no pass which ran last time has been left out.
+            Some(VisitState::Skipped(last)) => {
+                let same = if slot == WINDOW_PASS {
+                    last.passes == state.post.passes
+                } else {
+                    last.passes[slot] == state.post.passes[slot]
+                };
+
+                if same {
+                    Visit::Skip
+                } else {
+                    state.visit = Some(VisitState::Visited);
+                    Visit::Restore(Box::new(before))
+                }
+            }
+        };
+
+        if slot == WINDOW_PASS && matches!(state.visit, Some(VisitState::Visited)) {
+            let record = PostLayoutRecord {
+                passes: state.post.passes,
+                summary: state.post.summary.or(last.and_then(|last| last.summary)),
+                paint_index: state.post.paint_index.or(last.and_then(|last| last.paint_index)),
+            };
+
+            if last != Some(record) {
+                self.cache.set_post_layout_record(node, record);
+            }
+        }
+
+        visit
+    }
+
+    fn skipped(&self, node: &Self::Node) -> Option<PostLayoutRecord> {
+        match &self.nodes.get(&node.key())?.visit {
+            Some(VisitState::Skipped(last)) => Some(**last),
+            _ => None,
+        }
+    }
+
+    fn unskip(&mut self, node: &Self::Node) -> [Option<PassRecord>; PASSES] {
+        let state = self.state(node);
+        state.visit = Some(VisitState::Visited);
+        state.post.passes
+    }
+
+    fn set_summary(&mut self, node: &Self::Node, summary: Summary) {
+        self.state(node).post.summary = Some(summary);
+    }
+
+    fn set_paint_index(&mut self, node: &Self::Node, index: usize) {
+        if let Some(state) = self.nodes.get_mut(&node.key()) {
+            state.post.paint_index = Some(index);
+            if matches!(state.visit, Some(VisitState::Visited)) {
+                return;
+            }
+        }
+
+        // The records of nodes which are not visited by the last pass are updated here instead.
+        let last = self.cache.post_layout_record(node);
+        let record = PostLayoutRecord { paint_index: Some(index), ..last.unwrap_or_default() };
+        if last != Some(record) {
+            self.cache.set_post_layout_record(node, record);
+        }
+    }
+}
+
+impl<C: Cache> Cache for LayoutCache<'_, C> {
+    type Node = C::Node;
+
+    fn width(&self, node: &Self::Node) -> Scalar {
+        self.cache.width(node)
+    }
+
+    fn height(&self, node: &Self::Node) -> Scalar {
+        self.cache.height(node)
+    }
+
+    fn posx(&self, node: &Self::Node) -> Scalar {
+        self.cache.posx(node)
+    }
+
+    fn posy(&self, node: &Self::Node) -> Scalar {
+        self.cache.posy(node)
+    }
+
+    fn set_bounds(&mut self, node: &Self::Node, posx: Scalar, posy: Scalar, width: Scalar, height: Scalar) {
+        self.cache.set_bounds(node, posx, posy, width, height);
+    }
+
+    fn set_content_size(&mut self, node: &Self::Node, width: Scalar, height: Scalar) {
+        self.cache.set_content_size(node, width, height);
+    }
+
+    fn set_clip(&mut self, node: &Self::Node, posx: Scalar, posy: Scalar, width: Scalar, height: Scalar) {
+        self.cache.set_clip(node, posx, posy, width, height);
+    }
+
+    fn clip(&self, node: &Self::Node) -> Option<(Scalar, Scalar, Scalar, Scalar)> {
+        self.cache.clip(node)
+    }
+
+    fn set_stacking_order(&mut self, node: &Self::Node, index: usize) {
+        self.cache.set_stacking_order(node, index);
+    }
+
+    fn set_window_bounds(&mut self, node: &Self::Node, posx: Scalar, posy: Scalar, width: Scalar, height: Scalar) {
+        self.cache.set_window_bounds(node, posx, posy, width, height);
+    }
+
+    fn set_transform(&mut self, node: &Self::Node, transform: Transform) {
+        self.cache.set_transform(node, transform);
+    }
+
+    fn layout_record(&self, node: &Self::Node) -> Option<LayoutRecord> {
+        self.cache.layout_record(node)
+    }
+
+    fn set_layout_record(&mut self, node: &Self::Node, record: LayoutRecord) {
+        self.cache.set_layout_record(node, record);
+    }
+
+    fn post_layout_record(&self, node: &Self::Node) -> Option<PostLayoutRecord> {
+        self.cache.post_layout_record(node)
+    }
+
+    fn set_post_layout_record(&mut self, node: &Self::Node, record: PostLayoutRecord) {
+        self.cache.set_post_layout_record(node, record);
+    }
+
+    fn content_measurement(
+        &self,
+        node: &Self::Node,
+        width: Option<Scalar>,
+        height: Option<Scalar>,
+    ) -> Option<(Scalar, Scalar)> {
+        self.nodes
+            .get(&node.key())
+            .and_then(|state| find_measurement(&state.measurements, width, height))
+            .or_else(|| self.cache.content_measurement(node, width, height))
+    }
+
+    fn set_content_measurement(
+        &mut self,
+        node: &Self::Node,
+        width: Option<Scalar>,
+        height: Option<Scalar>,
+        size: (Scalar, Scalar),
+    ) {
+        self.cache.set_content_measurement(node, width, height, size);
+
+        // Measurements are only kept for the layout if the wrapped cache does not store them itself.
+        if self.cache.content_measurement(node, width, height).is_none() {
+            self.state(node).measurements.push((width, height, size));
+        }
+    }
+}
+
+/// The constraints which a node was last laid out with, along with the results of that layout, used to skip the
+/// layout of the node and its descendants when the constraints are unchanged.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LayoutRecord {
+    /// The layout type of the parent of the node.
+    pub parent_layout_type: LayoutType,
+    /// The space given to the node on the main axis of its parent.
+    pub parent_main: Scalar,
+    /// The space given to the node on the cross axis of its parent.
+    pub parent_cross: Scalar,
+    /// The unit context of the parent of the node.
+    pub context: UnitContext,
+    /// The computed size of the node.
+    pub size: Size,
+    /// The horizontal position of the node as set by the layout of its parent, before any adjustments made after
+    /// layout, such as sticky positioning or pixel snapping.
+    pub posx: Scalar,
+    /// The vertical position of the node as set by the layout of its parent, before any adjustments made after layout.
+    pub posy: Scalar,
+    /// The width of the node as set by the layout of its parent, before any adjustments made after layout.
+    pub width: Scalar,
+    /// The height of the node as set by the layout of its parent, before any adjustments made after layout.
+    pub height: Scalar,
+    /// The widths of the left, top, right and bottom borders of the node, as resolved by its layout.
+    pub border: [Scalar; 4],
+}
+
+/// The inputs which the passes after layout were last given for a node, used to skip the descendants of the node in
+/// those passes when its layout is skipped and the inputs are unchanged.
+///
+/// The contents of the record are private to the layout, so the cache only needs to store it.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct PostLayoutRecord {
+    /// The input of each pass and the bounds of the node when the pass reached it.
+    pub(crate) passes: [Option<PassRecord>; PASSES],
+    /// A summary of the subtree of the node.
+    pub(crate) summary: Option<Summary>,
+    /// The index of the node in the paint order.
+    pub(crate) paint_index: Option<usize>,
 }
 
 /// Helper trait for getting/setting node position/size in a direction agnostic way.
@@ -28,10 +484,10 @@ pub(crate) trait CacheExt: Cache {
         &mut self,
         node: &Self::Node,
         parent_layout_type: LayoutType,
-        main_pos: f32,
-        cross_pos: f32,
-        main: f32,
-        cross: f32,
+        main_pos: Scalar,
+        cross_pos: Scalar,
+        main: Scalar,
+        cross: Scalar,
     ) {
         match parent_layout_type {
             LayoutType::Row | LayoutType::Overlay => self.set_bounds(node, main_pos, cross_pos, main, cross),
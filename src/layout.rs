@@ -1,17 +1,20 @@
 use smallvec::SmallVec;
 
 use crate::{
-    Alignment, Cache, CacheExt, Direction, LayoutType, LayoutWrap, Node, NodeExt, PositionType, Size, Units, Units::*,
+    Alignment, Cache, CacheExt, Direction, LayoutRecord, LayoutState, LayoutType, LayoutWrap, Node, NodeExt,
+    NumericMode, Overflow, Placement, PositionType, Scalar, Size, Transform, UnitContext, Units, Units::*, Visit,
 };
 
-const DEFAULT_MIN: f32 = -f32::MAX;
-const DEFAULT_MAX: f32 = f32::MAX;
-const DEFAULT_BORDER_WIDTH: f32 = 0.0;
+const DEFAULT_MIN: Scalar = -Scalar::MAX;
+const DEFAULT_MAX: Scalar = Scalar::MAX;
+const DEFAULT_BORDER_WIDTH: Scalar = 0.0;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ItemType {
+    Before,
     Size,
     After,
+    Between,
 }
 
 /// Represents a space or size which has stretch units.
@@ -20,96 +23,1538 @@ struct StretchItem {
     // The child index of the item.
     index: usize,
     // The stretch factor of the item.
-    factor: f32,
-    // The type of stretch item, either space-before, size, or space-after.
+    factor: Scalar,
+    // The type of stretch item, either space-before, size, space-after, or the gap after the item.
     item_type: ItemType,
     // The violation of the stretch item after clamping.
-    violation: f32,
+    violation: Scalar,
     // The computed size of the stretch item.
-    computed: f32,
+    computed: Scalar,
     // The measured size from the current flex iteration.
-    measured: f32,
+    measured: Scalar,
     // Whether or not the stretch item is frozen.
     frozen: bool,
     // The minimum size of the stretch item.
-    min: f32,
+    min: Scalar,
     // The maximum size of the stretch item.
-    max: f32,
+    max: Scalar,
+}
+
+/// Divides `free_space` between stretch items in proportion to their `factors`, rounded to whole pixels, or to
+/// multiples of the [`step`](NumericMode::step) of a fixed-point `numeric_mode`.
+///
+/// Each item receives the floor of its exact share, and the steps left over are then given one at a time to the items
+/// with the largest remainders, with ties going to the earlier item. The shares therefore sum to the rounded free
+/// space, rather than drifting by a step or more as they would if each share were rounded separately.
+fn distribute_stretch(
+    factors: impl IntoIterator<Item = Scalar>,
+    free_space: Scalar,
+    numeric_mode: NumericMode,
+) -> SmallVec<[Scalar; 32]> {
+    let step = numeric_mode.step().unwrap_or(1.0);
+    let factors = factors.into_iter().collect::<SmallVec<[Scalar; 32]>>();
+    let flex_sum: Scalar = factors.iter().sum();
+
+    if flex_sum <= 0.0 {
+        return factors.iter().map(|_| 0.0).collect();
+    }
+
+    let free_steps = free_space / step;
+    let exact = factors.iter().map(|factor| factor * free_steps / flex_sum).collect::<SmallVec<[Scalar; 32]>>();
+    let mut shares = exact.iter().map(|share| share.floor()).collect::<SmallVec<[Scalar; 32]>>();
+
+    let leftover = (free_steps.round() - shares.iter().sum::<Scalar>()).round().max(0.0) as usize;
+
+    // The sort is stable, so items with equal remainders keep their order.
+    let mut order = (0..shares.len()).collect::<SmallVec<[usize; 32]>>();
+    order.sort_by(|a, b| (exact[*b] - shares[*b]).total_cmp(&(exact[*a] - shares[*a])));
+
+    for index in order.into_iter().take(leftover) {
+        shares[index] += 1.0;
+    }
+
+    shares.iter().map(|share| share * step).collect()
 }
 
 impl StretchItem {
-    pub fn new(index: usize, factor: f32, item_type: ItemType, min: f32, max: f32) -> Self {
+    pub fn new(index: usize, factor: Scalar, item_type: ItemType, min: Scalar, max: Scalar) -> Self {
         Self { index, factor, item_type, violation: 0.0, computed: 0.0, measured: 0.0, frozen: false, min, max }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct ChildNode<'a, N: Node> {
-    // A reference to the node.
-    node: &'a N,
-    // Computed cross size of the node.
-    cross: f32,
-    // Computed main size of the node.
-    main: f32,
+#[derive(Debug, Copy, Clone)]
+struct ChildNode<'a, N: Node> {
+    // A reference to the node.
+    node: &'a N,
+    // Computed cross size of the node.
+    cross: Scalar,
+    // Computed main size of the node.
+    main: Scalar,
+    // Computed space before the node on the main-axis.
+    main_before: Scalar,
+    // Computed space after the node on the main-axis.
+    main_after: Scalar,
+    // Computed gap between the node and the next node on the main-axis.
+    main_between: Scalar,
+    // Last parent constraints used to lay out this child.
+    last_layout_main: Scalar,
+    last_layout_cross: Scalar,
+    has_layout_constraints: bool,
+}
+
+impl<N: Node> ChildNode<'_, N> {
+    /// Returns the total space taken up by the node on the main-axis, including its spacing and the gap after it.
+    fn main_total(&self) -> Scalar {
+        self.main_before + self.main + self.main_after + self.main_between
+    }
+}
+
+fn flip_alignment_horizontal(alignment: Alignment) -> Alignment {
+    match alignment {
+        Alignment::TopLeft => Alignment::TopRight,
+        Alignment::TopRight => Alignment::TopLeft,
+        Alignment::Left => Alignment::Right,
+        Alignment::Right => Alignment::Left,
+        Alignment::BottomLeft => Alignment::BottomRight,
+        Alignment::BottomRight => Alignment::BottomLeft,
+        alignment => alignment,
+    }
+}
+
+#[inline]
+fn same_scalar(a: Scalar, b: Scalar) -> bool {
+    a.to_bits() == b.to_bits()
+}
+
+fn alignment_fractions(alignment: Alignment) -> (Scalar, Scalar) {
+    // Convert alignment into normalized horizontal/vertical fractions in [0, 1].
+    // These fractions are later multiplied by available free space.
+    match alignment {
+        Alignment::TopLeft => (0.0, 0.0),
+        Alignment::TopCenter => (0.5, 0.0),
+        Alignment::TopRight => (1.0, 0.0),
+        Alignment::Left => (0.0, 0.5),
+        Alignment::Center => (0.5, 0.5),
+        Alignment::Right => (1.0, 0.5),
+        Alignment::BottomLeft => (0.0, 1.0),
+        Alignment::BottomCenter => (0.5, 1.0),
+        Alignment::BottomRight => (1.0, 1.0),
+    }
+}
+
+fn absolute_axis_position(before: Units, after: Units, parent_size: Scalar, child_size: Scalar) -> Scalar {
+    // Resolve a child position on one axis from before/after offsets.
+    // This is shared by absolute positioning in stack and overlay layouts.
+    match (before, after) {
+        (Pixels(val), _) => val,
+        (Percentage(val), _) => val * 0.01 * parent_size,
+        (_, Pixels(val)) => parent_size - val - child_size,
+        (_, Percentage(val)) => parent_size - child_size - val * 0.01 * parent_size,
+        (Stretch(b), Stretch(a)) => {
+            if b == a {
+                (parent_size - child_size) * 0.5
+            } else {
+                (parent_size - child_size) * (b / (b + a))
+            }
+        }
+        (Stretch(_), Auto) => parent_size - child_size,
+        (Auto, Stretch(_)) => 0.0,
+        _ => 0.0,
+    }
+}
+
+/// The space on one side of a relative child in a stack or wrap layout, along with its bounds.
+#[derive(Debug, Copy, Clone)]
+struct Margin {
+    units: Units,
+    min: Units,
+    max: Units,
+}
+
+impl Margin {
+    /// Returns the stretch factor of the margin, or zero if the margin is not flexible.
+    fn factor(&self) -> Scalar {
+        if let Stretch(factor) = self.units {
+            factor
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the size of a non-flexible margin in pixels. Stretch margins resolve to zero.
+    fn fixed(&self, parent: Scalar) -> Scalar {
+        if self.units.is_stretch() {
+            0.0
+        } else {
+            self.units.to_px_clamped(parent, 0.0, self.min, self.max)
+        }
+    }
+
+    /// Returns the size of a stretch margin given its share of the free space, clamped to its bounds.
+    fn clamp(&self, size: Scalar, parent: Scalar) -> Scalar {
+        size.min(self.max.to_px(parent, DEFAULT_MAX)).max(self.min.to_px(parent, DEFAULT_MIN))
+    }
+}
+
+/// The margins of a relative child in a stack or wrap layout, in the axes of the parent.
+#[derive(Debug, Copy, Clone)]
+struct Margins {
+    main_before: Margin,
+    main_after: Margin,
+    cross_before: Margin,
+    cross_after: Margin,
+}
+
+impl Margins {
+    /// Returns the margins of a relative child, accounting for the logical swap of left and right under RTL.
+    fn new<N: Node>(
+        child: &N,
+        store: &<N as Node>::Store,
+        layout_type: LayoutType,
+        is_rtl: bool,
+        ctx: &UnitContext,
+    ) -> Self {
+        // The insets of a sticky child are thresholds for its sticky position rather than spacing.
+        if child.position_type(store).unwrap_or_default() == PositionType::Sticky {
+            let none = Margin { units: Auto, min: Auto, max: Auto };
+            return Self { main_before: none, main_after: none, cross_before: none, cross_after: none };
+        }
+
+        let main_before = Margin {
+            units: child.main_before(store, layout_type, ctx),
+            min: child.min_main_before(store, layout_type, ctx),
+            max: child.max_main_before(store, layout_type, ctx),
+        };
+        let main_after = Margin {
+            units: child.main_after(store, layout_type, ctx),
+            min: child.min_main_after(store, layout_type, ctx),
+            max: child.max_main_after(store, layout_type, ctx),
+        };
+        let cross_before = Margin {
+            units: child.cross_before(store, layout_type, ctx),
+            min: child.min_cross_before(store, layout_type, ctx),
+            max: child.max_cross_before(store, layout_type, ctx),
+        };
+        let cross_after = Margin {
+            units: child.cross_after(store, layout_type, ctx),
+            min: child.min_cross_after(store, layout_type, ctx),
+            max: child.max_cross_after(store, layout_type, ctx),
+        };
+
+        match layout_type {
+            LayoutType::Row if is_rtl => {
+                Self { main_before: main_after, main_after: main_before, cross_before, cross_after }
+            }
+            LayoutType::Column if is_rtl => {
+                Self { main_before, main_after, cross_before: cross_after, cross_after: cross_before }
+            }
+            _ => Self { main_before, main_after, cross_before, cross_after },
+        }
+    }
+
+    /// Returns the sum of the non-flexible cross-axis margins.
+    fn fixed_cross(&self, parent_cross: Scalar) -> Scalar {
+        self.cross_before.fixed(parent_cross) + self.cross_after.fixed(parent_cross)
+    }
+
+    /// Returns true if either cross-axis margin is flexible, in which case the margins position the child
+    /// instead of the alignment of the parent.
+    fn is_cross_stretch(&self) -> bool {
+        self.cross_before.units.is_stretch() || self.cross_after.units.is_stretch()
+    }
+
+    /// Returns the size of a child with a stretch cross size of the given `factor`, which shares the free
+    /// cross space with any stretch cross-axis margins.
+    fn stretch_cross(&self, parent_cross: Scalar, factor: Scalar) -> Scalar {
+        let free = parent_cross - self.fixed_cross(parent_cross);
+        let margin_factor = self.cross_before.factor() + self.cross_after.factor();
+
+        if margin_factor > 0.0 {
+            free * factor / (factor + margin_factor)
+        } else {
+            free
+        }
+    }
+
+    /// Resolves the cross-axis margins of a child with the given cross size, returning the (before, after) margins.
+    fn resolve_cross(&self, parent_cross: Scalar, cross: Scalar) -> (Scalar, Scalar) {
+        let mut before = self.cross_before.fixed(parent_cross);
+        let mut after = self.cross_after.fixed(parent_cross);
+        let margin_factor = self.cross_before.factor() + self.cross_after.factor();
+
+        if margin_factor > 0.0 {
+            let free = (parent_cross - before - after - cross).max(0.0);
+
+            if self.cross_before.units.is_stretch() {
+                before = self.cross_before.clamp(free * self.cross_before.factor() / margin_factor, parent_cross);
+            }
+
+            if self.cross_after.units.is_stretch() {
+                after = self.cross_after.clamp(free * self.cross_after.factor() / margin_factor, parent_cross);
+            }
+        }
+
+        (before, after)
+    }
+
+    /// Returns the position of a child with the given cross size within the cross space, offset by its margins.
+    fn cross_position(&self, parent_cross: Scalar, cross: Scalar, alignment: Scalar) -> Scalar {
+        let (before, after) = self.resolve_cross(parent_cross, cross);
+
+        if self.is_cross_stretch() {
+            before
+        } else {
+            before + alignment * (parent_cross - before - cross - after)
+        }
+    }
+}
+
+/// An axis-aligned box in the coordinate space of a node, relative to its top-left corner.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct Bounds {
+    x: Scalar,
+    y: Scalar,
+    width: Scalar,
+    height: Scalar,
+}
+
+impl Bounds {
+    /// Returns the bounds with the width and height swapped, for converting main/cross bounds of a column.
+    fn transpose(self) -> Self {
+        Self { x: self.y, y: self.x, width: self.height, height: self.width }
+    }
+}
+
+/// The number of passes after layout which record their inputs for each node.
+pub(crate) const PASSES: usize = 6;
+
+/// The index of the last pass after layout, which computes the window-space bounds of each node.
+pub(crate) const WINDOW_PASS: usize = PASSES - 1;
+
+/// The arguments which one of the passes after layout is given for a node, other than the node itself.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum PassInput {
+    OutOfFlow { offset: (Scalar, Scalar), root: (Scalar, Scalar), containing_block: Option<Bounds>, ctx: UnitContext },
+    Sticky { view_x: (Scalar, Scalar), view_y: (Scalar, Scalar), shift: (Scalar, Scalar), ctx: UnitContext },
+    Snap { parent: (Scalar, Scalar), parent_snapped: (Scalar, Scalar), scale_factor: Scalar },
+    Clip { clip_x: ClipRange, clip_y: ClipRange, ctx: UnitContext },
+    Window { parent: Transform },
+}
+
+impl PassInput {
+    /// Returns the index of the pass in the order in which the passes run, given the passes which have already run
+    /// for the node.
+    ///
+    /// Edges may be snapped twice, first to the device pixel grid and then to the fixed-point grid, which are told
+    /// apart by their scale factor.
+    pub(crate) fn index(&self, passes: &[Option<PassRecord>; PASSES]) -> usize {
+        match self {
+            PassInput::OutOfFlow { .. } => 0,
+            PassInput::Sticky { .. } => 1,
+            PassInput::Snap { scale_factor, .. } => match passes[2] {
+                Some(PassRecord { input: PassInput::Snap { scale_factor: first, .. }, .. })
+                    if first != *scale_factor =>
+                {
+                    3
+                }
+                _ => 2,
+            },
+            PassInput::Clip { .. } => 4,
+            PassInput::Window { .. } => WINDOW_PASS,
+        }
+    }
+}
+
+/// The input of one of the passes after layout for a node, along with the bounds of the node when the pass reached it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct PassRecord {
+    pub(crate) input: PassInput,
+    pub(crate) bounds: [Scalar; 4],
+}
+
+/// A summary of the visible subtree of a node, used to decide whether the passes after layout can skip it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct Summary {
+    /// The number of visible nodes in the subtree, including the node itself.
+    pub(crate) nodes: usize,
+    /// Whether any descendant is an absolute node placed next to an anchor.
+    pub(crate) anchored: bool,
+    /// Whether any descendant has a z-index.
+    pub(crate) z_index: bool,
+}
+
+/// Returns true if a pass after layout should visit the descendants of `node`, given the input of the pass.
+///
+/// The descendants of a node whose layout was skipped are left as they were after the previous layout, as long as
+/// each pass is given the same input for the node as it was then. Otherwise they are restored from their layout
+/// records and visited again.
+fn visit_descendants<N, C>(
+    node: &N,
+    input: PassInput,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+) -> bool
+where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    match cache.visit(node, input) {
+        Visit::Skip => false,
+        Visit::Descend => true,
+        Visit::Restore(passes) => {
+            revisit(node, &passes, cache, tree, store);
+            true
+        }
+    }
+}
+
+/// Restores the descendants of `node` from their layout records and runs the given passes after layout for them
+/// again, with the bounds which `node` had when each pass first reached it.
+///
+/// Out-of-flow nodes keep the bounds they are given by that pass in their layout records, so it is not run again.
+fn revisit<N, C>(
+    node: &N,
+    passes: &[Option<PassRecord>; PASSES],
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+) where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let (posx, posy, width, height) = (cache.posx(node), cache.posy(node), cache.width(node), cache.height(node));
+    restore_layout(node, cache, tree, store);
+
+    for record in passes.iter().flatten() {
+        let [pass_posx, pass_posy, pass_width, pass_height] = record.bounds;
+        cache.set_bounds(node, pass_posx, pass_posy, pass_width, pass_height);
+
+        match record.input {
+            PassInput::OutOfFlow { .. } => {}
+            PassInput::Sticky { view_x, view_y, shift, ctx } => {
+                layout_sticky(node, view_x, view_y, shift, cache, tree, store, &ctx)
+            }
+            PassInput::Snap { parent, parent_snapped, scale_factor } => {
+                snap_to_pixels(node, parent, parent_snapped, scale_factor, cache, tree, store)
+            }
+            PassInput::Clip { clip_x, clip_y, ctx } => layout_clip(node, clip_x, clip_y, cache, tree, store, &ctx),
+            PassInput::Window { parent } => window_bounds(node, parent, cache, tree, store),
+        }
+    }
+
+    cache.set_bounds(node, posx, posy, width, height);
+}
+
+/// Reports the content size of `node` to the cache and then translates its relative children by its scroll offsets,
+/// once the children have been positioned within the `content` box of the node.
+///
+/// The content size is the union of the `padding` box and the bounds of the relative children, extended by the
+/// padding of the node, before any scrolling is applied.
+///
+/// The scroll offsets are added to the aligned position of each child, so scrolling never changes the alignment of
+/// the content. If the node clamps its scroll offsets, each offset is limited to the range which keeps the content
+/// within the content box where possible. Absolute children are not scrolled.
+fn scroll_content<N, C>(
+    node: &N,
+    content: Bounds,
+    padding: Bounds,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+) where
+    N: Node,
+    C: Cache<Node = N>,
+{
+    let children = node
+        .children(tree)
+        .filter(|child| child.visible(store))
+        .filter(|child| child.position_type(store).unwrap_or_default().is_in_flow())
+        .collect::<SmallVec<[&N; 32]>>();
+
+    // Bounds of the content box, grown to include the relative children.
+    let (mut left, mut top) = (content.x, content.y);
+    let (mut right, mut bottom) = (content.x + content.width, content.y + content.height);
+    for child in children.iter() {
+        left = left.min(cache.posx(child));
+        top = top.min(cache.posy(child));
+        right = right.max(cache.posx(child) + cache.width(child));
+        bottom = bottom.max(cache.posy(child) + cache.height(child));
+    }
+
+    // Padding on each side of the content box.
+    let (padding_left, padding_top) = (content.x - padding.x, content.y - padding.y);
+    let padding_right = padding.x + padding.width - content.x - content.width;
+    let padding_bottom = padding.y + padding.height - content.y - content.height;
+
+    cache.set_content_size(
+        node,
+        right - left + padding_left + padding_right,
+        bottom - top + padding_top + padding_bottom,
+    );
+
+    let mut scroll_x = node.horizontal_scroll(store).unwrap_or_default();
+    let mut scroll_y = node.vertical_scroll(store).unwrap_or_default();
+
+    if scroll_x == 0.0 && scroll_y == 0.0 {
+        return;
+    }
+
+    if node.clamp_scroll(store).unwrap_or_default() {
+        // Content which overflows the end of the content box can be scrolled back, and vice versa.
+        scroll_x = scroll_x.clamp(content.x + content.width - right, content.x - left);
+        scroll_y = scroll_y.clamp(content.y + content.height - bottom, content.y - top);
+    }
+
+    for child in children {
+        let posx = cache.posx(child) + scroll_x;
+        let posy = cache.posy(child) + scroll_y;
+        let (child_width, child_height) = (cache.width(child), cache.height(child));
+        cache.set_bounds(child, posx, posy, child_width, child_height);
+    }
+}
+
+/// Returns the padding box of a node with the given size, in its own coordinate space.
+///
+/// The borders are those resolved by the layout of the node, as percentage borders may have been resolved against a
+/// different size than the final size of the node.
+fn padding_box<N, C>(
+    node: &N,
+    width: Scalar,
+    height: Scalar,
+    cache: &C,
+    store: &<N as Node>::Store,
+    ctx: &UnitContext,
+) -> Bounds
+where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let [border_left, border_top, border_right, border_bottom] = cache.border(node).unwrap_or_else(|| {
+        [
+            node.border_left(store).unwrap_or_default().resolve(ctx).to_px(width, DEFAULT_BORDER_WIDTH),
+            node.border_top(store).unwrap_or_default().resolve(ctx).to_px(height, DEFAULT_BORDER_WIDTH),
+            node.border_right(store).unwrap_or_default().resolve(ctx).to_px(width, DEFAULT_BORDER_WIDTH),
+            node.border_bottom(store).unwrap_or_default().resolve(ctx).to_px(height, DEFAULT_BORDER_WIDTH),
+        ]
+    });
+
+    Bounds {
+        x: border_left,
+        y: border_top,
+        width: width - border_left - border_right,
+        height: height - border_top - border_bottom,
+    }
+}
+
+/// Returns the position of a sticky node on one axis, given its in-flow position and size.
+///
+/// The `before` and `after` insets are thresholds from the edges of `view`, the visible range of the nearest scroll
+/// container of the node, with `before` taking precedence. The node is not pushed outside of `bounds`, the range of
+/// its parent, beyond its in-flow position.
+fn sticky_position(
+    pos: Scalar,
+    size: Scalar,
+    before: Units,
+    after: Units,
+    view: (Scalar, Scalar),
+    bounds: Option<(Scalar, Scalar)>,
+) -> Scalar {
+    let view_size = view.1 - view.0;
+    let mut target = pos;
+
+    if after.is_pixels() || after.is_percentage() {
+        target = target.min(view.1 - after.to_px(view_size, 0.0) - size);
+    }
+
+    if before.is_pixels() || before.is_percentage() {
+        target = target.max(view.0 + before.to_px(view_size, 0.0));
+    }
+
+    match bounds {
+        Some((_, end)) if target > pos => target.min((end - size).max(pos)),
+        Some((start, _)) if target < pos => target.max(start.min(pos)),
+        _ => target,
+    }
+}
+
+/// Moves the sticky descendants of `node` once scroll offsets have been applied, keeping each within the visible
+/// area of its nearest scroll container.
+///
+/// `view_x` and `view_y` are the visible ranges of the nearest scroll container of the node, in the coordinate space
+/// of its parent. A node with [`Overflow::Scroll`] on an axis is the scroll container of its descendants on that axis.
+///
+/// `shift` is the distance the node has been moved along with its sticky ancestors, which is cancelled out for fixed
+/// descendants so that they stay where they were placed against the root rect.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn layout_sticky<N, C>(
+    node: &N,
+    view_x: (Scalar, Scalar),
+    view_y: (Scalar, Scalar),
+    shift: (Scalar, Scalar),
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+    ctx: &UnitContext,
+) where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    if !visit_descendants(node, PassInput::Sticky { view_x, view_y, shift, ctx: *ctx }, cache, tree, store) {
+        return;
+    }
+
+    let (posx, posy, width, height) = (cache.posx(node), cache.posy(node), cache.width(node), cache.height(node));
+    let ctx = &UnitContext { width: Some(width), height: Some(height), ..node.unit_context(store, ctx) };
+
+    let padding = padding_box(node, width, height, cache, store, ctx);
+    let scrolls_x = node.horizontal_overflow(store).unwrap_or_default() == Overflow::Scroll;
+    let scrolls_y = node.vertical_overflow(store).unwrap_or_default() == Overflow::Scroll;
+
+    // Visible area for the children of the node, in the coordinate space of the node.
+    let view_x = if scrolls_x { (padding.x, padding.x + padding.width) } else { (view_x.0 - posx, view_x.1 - posx) };
+    let view_y = if scrolls_y { (padding.y, padding.y + padding.height) } else { (view_y.0 - posy, view_y.1 - posy) };
+
+    // Sticky children of a scroll container may move anywhere within its scrollable content.
+    let bounds_x = if scrolls_x { None } else { Some((0.0, width)) };
+    let bounds_y = if scrolls_y { None } else { Some((0.0, height)) };
+
+    for child in node.children(tree).filter(|child| child.visible(store)) {
+        let mut child_shift = shift;
+
+        let position_type = child.position_type(store).unwrap_or_default();
+        if position_type == PositionType::Fixed {
+            let (child_posx, child_posy) = (cache.posx(child) - shift.0, cache.posy(child) - shift.1);
+            let (child_width, child_height) = (cache.width(child), cache.height(child));
+            cache.set_bounds(child, child_posx, child_posy, child_width, child_height);
+            child_shift = (0.0, 0.0);
+        }
+
+        if position_type == PositionType::Sticky {
+            let child_ctx = &child.unit_context(store, ctx);
+            let (child_width, child_height) = (cache.width(child), cache.height(child));
+
+            let child_posx = sticky_position(
+                cache.posx(child),
+                child_width,
+                child.left(store).unwrap_or_default().resolve(child_ctx),
+                child.right(store).unwrap_or_default().resolve(child_ctx),
+                view_x,
+                bounds_x,
+            );
+
+            let child_posy = sticky_position(
+                cache.posy(child),
+                child_height,
+                child.top(store).unwrap_or_default().resolve(child_ctx),
+                child.bottom(store).unwrap_or_default().resolve(child_ctx),
+                view_y,
+                bounds_y,
+            );
+
+            child_shift.0 += child_posx - cache.posx(child);
+            child_shift.1 += child_posy - cache.posy(child);
+            cache.set_bounds(child, child_posx, child_posy, child_width, child_height);
+        }
+
+        layout_sticky(child, view_x, view_y, child_shift, cache, tree, store, ctx);
+    }
+}
+
+/// Lays out an out-of-flow node by its insets within `block`, the root-space bounds of its containing block.
+///
+/// `offset_x` and `offset_y` are the position of the parent of the node relative to the root, which is subtracted so
+/// that the bounds of the node are stored relative to its parent.
+#[allow(clippy::too_many_arguments)]
+fn layout_in_block<N, C>(
+    node: &N,
+    block: Bounds,
+    offset_x: Scalar,
+    offset_y: Scalar,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+    sublayout: &mut <N as Node>::SubLayout<'_>,
+    ctx: &UnitContext,
+) where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let left = node.main_before(store, LayoutType::Overlay, ctx);
+    let right = node.main_after(store, LayoutType::Overlay, ctx);
+    let top = node.cross_before(store, LayoutType::Overlay, ctx);
+    let bottom = node.cross_after(store, LayoutType::Overlay, ctx);
+
+    // Stretch sizing consumes the size of the containing block after offsets, constrained by the size constraints of
+    // the node, with the minimum taking precedence over the maximum.
+    let width = if node.main(store, LayoutType::Overlay, ctx).is_stretch() {
+        let min_width = node.min_main(store, LayoutType::Overlay, ctx).to_px(block.width, DEFAULT_MIN);
+        let max_width = node.max_main(store, LayoutType::Overlay, ctx).to_px(block.width, DEFAULT_MAX);
+
+        let width = block.width - left.to_px(block.width, 0.0) - right.to_px(block.width, 0.0);
+        width.min(max_width).max(min_width)
+    } else {
+        block.width
+    };
+
+    let height = if node.cross(store, LayoutType::Overlay, ctx).is_stretch() {
+        let min_height = node.min_cross(store, LayoutType::Overlay, ctx).to_px(block.height, DEFAULT_MIN);
+        let max_height = node.max_cross(store, LayoutType::Overlay, ctx).to_px(block.height, DEFAULT_MAX);
+
+        let height = block.height - top.to_px(block.height, 0.0) - bottom.to_px(block.height, 0.0);
+        height.min(max_height).max(min_height)
+    } else {
+        block.height
+    };
+
+    let size = layout(node, LayoutType::Overlay, width, height, cache, tree, store, sublayout, ctx);
+
+    let posx = block.x + absolute_axis_position(left, right, block.width, size.main);
+    let posy = block.y + absolute_axis_position(top, bottom, block.height, size.cross);
+
+    cache.set_bounds(node, posx - offset_x, posy - offset_y, size.main, size.cross);
+
+    // The bounds are recorded so that they are restored along with the descendants of a clean ancestor.
+    record_bounds(node, cache);
+}
+
+/// Lays out the out-of-flow descendants of `node` which are positioned against a node other than their parent.
+///
+/// Fixed nodes, which are skipped by the layout of their parents, are laid out against the root rect of the given size.
+/// Absolute nodes are laid out against their parent as usual, unless the parent is not a
+/// [containing block](Node::containing_block) and an ancestor is, in which case they are laid out again against the
+/// padding box of the nearest such ancestor.
+///
+/// `offset_x` and `offset_y` are the position of `node` relative to the root, including the scroll offsets of its
+/// ancestors, and `containing_block` is the padding box of the nearest containing block among the ancestors of `node`,
+/// relative to the root. The bounds of repositioned nodes are stored relative
+/// to their parent, so that the scroll offsets of the ancestors between them and their containing block are cancelled
+/// out.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn layout_out_of_flow<N, C>(
+    node: &N,
+    offset_x: Scalar,
+    offset_y: Scalar,
+    root_width: Scalar,
+    root_height: Scalar,
+    containing_block: Option<Bounds>,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+    sublayout: &mut <N as Node>::SubLayout<'_>,
+    ctx: &UnitContext,
+) where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let input = PassInput::OutOfFlow {
+        offset: (offset_x, offset_y),
+        root: (root_width, root_height),
+        containing_block,
+        ctx: *ctx,
+    };
+    if !visit_descendants(node, input, cache, tree, store) {
+        return;
+    }
+
+    let is_containing_block = node.containing_block(store).unwrap_or_default();
+
+    // A node which is a containing block positions its absolute descendants within its padding box.
+    let containing_block = if is_containing_block {
+        let (width, height) = (cache.width(node), cache.height(node));
+        let ctx = &UnitContext { width: Some(width), height: Some(height), ..node.unit_context(store, ctx) };
+        let padding = padding_box(node, width, height, cache, store, ctx);
+
+        Some(Bounds { x: offset_x + padding.x, y: offset_y + padding.y, ..padding })
+    } else {
+        containing_block
+    };
+
+    let ctx = &node.unit_context(store, ctx);
+    let root = Bounds { x: 0.0, y: 0.0, width: root_width, height: root_height };
+
+    for child in node.children(tree).filter(|child| child.visible(store)) {
+        match child.position_type(store).unwrap_or_default() {
+            PositionType::Fixed => layout_in_block(child, root, offset_x, offset_y, cache, tree, store, sublayout, ctx),
+
+            // Absolute children of a containing block have already been laid out against it by their parent.
+            PositionType::Absolute if !is_containing_block => {
+                if let Some(block) = containing_block {
+                    layout_in_block(child, block, offset_x, offset_y, cache, tree, store, sublayout, ctx);
+                }
+            }
+
+            _ => {}
+        }
+
+        let (child_offset_x, child_offset_y) = (offset_x + cache.posx(child), offset_y + cache.posy(child));
+
+        layout_out_of_flow(
+            child,
+            child_offset_x,
+            child_offset_y,
+            root_width,
+            root_height,
+            containing_block,
+            cache,
+            tree,
+            store,
+            sublayout,
+            ctx,
+        );
+    }
+}
+
+/// Returns the position of a node with the given size placed next to `anchor`.
+fn placement_position(placement: Placement, anchor: Bounds, width: Scalar, height: Scalar) -> (Scalar, Scalar) {
+    let (side, align) = match placement {
+        Placement::AboveStart => (Placement::AboveStart, 0.0),
+        Placement::AboveCenter => (Placement::AboveStart, 0.5),
+        Placement::AboveEnd => (Placement::AboveStart, 1.0),
+        Placement::BelowStart => (Placement::BelowStart, 0.0),
+        Placement::BelowCenter => (Placement::BelowStart, 0.5),
+        Placement::BelowEnd => (Placement::BelowStart, 1.0),
+        Placement::LeftStart => (Placement::LeftStart, 0.0),
+        Placement::LeftCenter => (Placement::LeftStart, 0.5),
+        Placement::LeftEnd => (Placement::LeftStart, 1.0),
+        Placement::RightStart => (Placement::RightStart, 0.0),
+        Placement::RightCenter => (Placement::RightStart, 0.5),
+        Placement::RightEnd => (Placement::RightStart, 1.0),
+    };
+
+    let posx = anchor.x + (anchor.width - width) * align;
+    let posy = anchor.y + (anchor.height - height) * align;
+
+    match side {
+        Placement::AboveStart => (posx, anchor.y - height),
+        Placement::BelowStart => (posx, anchor.y + anchor.height),
+        Placement::LeftStart => (anchor.x - width, posy),
+        _ => (anchor.x + anchor.width, posy),
+    }
+}
+
+/// Returns the position of a node with the given size placed next to `anchor` within a viewport of the given size.
+///
+/// The placement is flipped to the opposite side of the anchor when the node would overflow the viewport and there is
+/// more space on the opposite side. The node is then shifted along the side of the anchor to stay within the viewport.
+fn anchored_position(
+    placement: Placement,
+    anchor: Bounds,
+    width: Scalar,
+    height: Scalar,
+    viewport_width: Scalar,
+    viewport_height: Scalar,
+) -> (Scalar, Scalar) {
+    // The space before and after the anchor on the axis of the side which the node is placed on.
+    let (size, anchor_start, anchor_size, viewport_size) = if placement.is_vertical() {
+        (height, anchor.y, anchor.height, viewport_height)
+    } else {
+        (width, anchor.x, anchor.width, viewport_width)
+    };
+    let space_before = anchor_start;
+    let space_after = viewport_size - anchor_start - anchor_size;
+
+    let is_before = matches!(
+        placement,
+        Placement::AboveStart
+            | Placement::AboveCenter
+            | Placement::AboveEnd
+            | Placement::LeftStart
+            | Placement::LeftCenter
+            | Placement::LeftEnd
+    );
+    let (space, opposite_space) = if is_before { (space_before, space_after) } else { (space_after, space_before) };
+
+    let placement = if size > space && opposite_space > space { placement.flip() } else { placement };
+    let (posx, posy) = placement_position(placement, anchor, width, height);
+
+    // Shift the node along the side of the anchor to stay within the viewport.
+    if placement.is_vertical() {
+        (posx.min(viewport_width - width).max(0.0), posy)
+    } else {
+        (posx, posy.min(viewport_height - height).max(0.0))
+    }
+}
+
+/// Records the visible nodes in the subtree of `node` in depth-first order, along with the position of the parent of
+/// each node relative to the root and the index of the end of the subtree of each node. The descendants of nodes which
+/// are skipped by the passes after layout are left out.
+fn flatten_tree<'t, N, C>(
+    node: &'t N,
+    parent_x: Scalar,
+    parent_y: Scalar,
+    cache: &C,
+    tree: &'t <N as Node>::Tree,
+    store: &<N as Node>::Store,
+    nodes: &mut Vec<(&'t N, Scalar, Scalar, usize)>,
+) where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let index = nodes.len();
+    nodes.push((node, parent_x, parent_y, index + 1));
+
+    if cache.skipped(node).is_some() {
+        return;
+    }
+
+    let (posx, posy) = (parent_x + cache.posx(node), parent_y + cache.posy(node));
+    for child in node.children(tree).filter(|child| child.visible(store)) {
+        flatten_tree(child, posx, posy, cache, tree, store, nodes);
+    }
+
+    nodes[index].3 = nodes.len();
+}
+
+/// Returns true if `node` is an absolute node placed next to an anchor.
+fn is_anchored<N: Node>(node: &N, store: &<N as Node>::Store) -> bool {
+    node.position_type(store).unwrap_or_default() == PositionType::Absolute && node.anchor(store).is_some()
+}
+
+/// Records a [`Summary`] of the subtree of each node in the output of [`flatten_tree`] which is not skipped.
+fn record_summaries<N, C>(nodes: &[(&N, Scalar, Scalar, usize)], cache: &mut C, store: &<N as Node>::Store)
+where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let mut summaries = vec![Summary { nodes: 1, anchored: false, z_index: false }; nodes.len()];
+
+    for index in (0..nodes.len()).rev() {
+        let (node, .., end) = nodes[index];
+
+        if let Some(summary) = cache.skipped(node).and_then(|last| last.summary) {
+            summaries[index] = summary;
+            continue;
+        }
+
+        let mut child = index + 1;
+        while child < end {
+            let (descendant, .., next) = nodes[child];
+            summaries[index].nodes += summaries[child].nodes;
+            summaries[index].anchored |= summaries[child].anchored || is_anchored(descendant, store);
+            summaries[index].z_index |= summaries[child].z_index || descendant.z_index(store).is_some();
+            child = next;
+        }
+
+        cache.set_summary(node, summaries[index]);
+    }
+}
+
+/// Places each absolute node with an [`anchor`](Node::anchor) next to its anchor, within the root rect of the given
+/// size.
+///
+/// Anchored nodes are placed in depth-first order, so a node may be anchored to a node which was itself placed next to
+/// an earlier anchor. The bounds of anchored nodes are stored relative to their parent.
+///
+/// Subtrees which are skipped by the passes after layout contain no anchored nodes. If an anchor is not found outside
+/// of them, they are visited again in case it is within one of them.
+pub(crate) fn layout_anchored<N, C>(
+    node: &N,
+    width: Scalar,
+    height: Scalar,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+) where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let mut nodes = Vec::new();
+    flatten_tree(node, -cache.posx(node), -cache.posy(node), cache, tree, store, &mut nodes);
+
+    let is_missing = |anchor_key| !nodes.iter().any(|(anchor, ..)| anchor.key() == anchor_key);
+    let missing = nodes
+        .iter()
+        .filter(|(child, ..)| is_anchored(*child, store))
+        .any(|(child, ..)| child.anchor(store).is_some_and(is_missing));
+
+    if missing {
+        let skipped =
+            nodes.iter().map(|(child, ..)| *child).filter(|child| cache.skipped(*child).is_some()).collect::<Vec<_>>();
+
+        if !skipped.is_empty() {
+            for child in skipped {
+                let passes = cache.unskip(child);
+                revisit(child, &passes, cache, tree, store);
+            }
+
+            nodes.clear();
+            flatten_tree(node, -cache.posx(node), -cache.posy(node), cache, tree, store, &mut nodes);
+        }
+    }
+
+    record_summaries(&nodes, cache, store);
+
+    for index in 0..nodes.len() {
+        let (child, parent_x, parent_y, end) = nodes[index];
+
+        if !is_anchored(child, store) {
+            continue;
+        }
+
+        let Some(anchor_key) = child.anchor(store) else {
+            continue;
+        };
+
+        let Some(&(anchor, anchor_parent_x, anchor_parent_y, _)) =
+            nodes.iter().find(|(anchor, ..)| anchor.key() == anchor_key)
+        else {
+            continue;
+        };
+
+        let anchor = Bounds {
+            x: anchor_parent_x + cache.posx(anchor),
+            y: anchor_parent_y + cache.posy(anchor),
+            width: cache.width(anchor),
+            height: cache.height(anchor),
+        };
+
+        let (child_width, child_height) = (cache.width(child), cache.height(child));
+        let placement = child.placement(store).unwrap_or_default();
+        let (posx, posy) = anchored_position(placement, anchor, child_width, child_height, width, height);
+
+        // Move the descendants of the node along with it.
+        let (dx, dy) = (posx - parent_x - cache.posx(child), posy - parent_y - cache.posy(child));
+        for entry in nodes[index + 1..end].iter_mut() {
+            entry.1 += dx;
+            entry.2 += dy;
+        }
+
+        cache.set_bounds(child, posx - parent_x, posy - parent_y, child_width, child_height);
+    }
+}
+
+/// Records the visible descendants of `node` which are in the same stacking context, in tree order, along with the
+/// descendants which form their own stacking context and their z-index. The descendants of nodes for which `descend`
+/// returns false are left out.
+fn collect_stacking_context<'t, N: Node>(
+    node: &'t N,
+    tree: &'t <N as Node>::Tree,
+    store: &<N as Node>::Store,
+    descend: &impl Fn(&N) -> bool,
+    flow: &mut Vec<&'t N>,
+    contexts: &mut Vec<(i32, &'t N)>,
+) {
+    for child in node.children(tree).filter(|child| child.visible(store)) {
+        if let Some(z_index) = child.z_index(store) {
+            contexts.push((z_index, child));
+        } else {
+            flow.push(child);
+            if descend(child) {
+                collect_stacking_context(child, tree, store, descend, flow, contexts);
+            }
+        }
+    }
+}
+
+/// Visits `node` and its visible descendants in the order in which they should be painted, from back to front.
+///
+/// The node is painted first, followed by the stacking contexts within it with a negative z-index, then the rest of
+/// its descendants in tree order, and then the stacking contexts with a z-index of zero or more. Stacking contexts with
+/// the same z-index are painted in tree order.
+pub(crate) fn visit_paint_order<'t, N: Node>(
+    node: &'t N,
+    tree: &'t <N as Node>::Tree,
+    store: &<N as Node>::Store,
+    visit: &mut impl FnMut(&'t N),
+) {
+    visit(node);
+
+    let mut flow = Vec::new();
+    let mut contexts = Vec::new();
+    collect_stacking_context(node, tree, store, &|_| true, &mut flow, &mut contexts);
+
+    // The sort is stable, so stacking contexts with the same z-index remain in tree order.
+    contexts.sort_by_key(|(z_index, _)| *z_index);
+    let split = contexts.partition_point(|(z_index, _)| *z_index < 0);
+
+    for (_, context) in &contexts[..split] {
+        visit_paint_order(*context, tree, store, visit);
+    }
+
+    for child in flow {
+        visit(child);
+    }
+
+    for (_, context) in &contexts[split..] {
+        visit_paint_order(*context, tree, store, visit);
+    }
+}
+
+/// Rounds the edges of `node` and its visible descendants to the device pixel grid, given the position of the parent
+/// of `node` relative to the root before and after rounding.
+///
+/// Each edge is rounded from its unrounded position relative to the root, so rounding errors do not accumulate down the
+/// tree, and the size of each node is the distance between its rounded edges.
+pub(crate) fn snap_to_pixels<N, C>(
+    node: &N,
+    parent: (Scalar, Scalar),
+    parent_snapped: (Scalar, Scalar),
+    scale_factor: Scalar,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+) where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let descend = visit_descendants(node, PassInput::Snap { parent, parent_snapped, scale_factor }, cache, tree, store);
+
+    let snap = |value: Scalar| (value * scale_factor).round() / scale_factor;
+
+    let left = parent.0 + cache.posx(node);
+    let top = parent.1 + cache.posy(node);
+    let right = left + cache.width(node);
+    let bottom = top + cache.height(node);
+
+    let (snapped_left, snapped_top) = (snap(left), snap(top));
+
+    cache.set_bounds(
+        node,
+        snapped_left - parent_snapped.0,
+        snapped_top - parent_snapped.1,
+        snap(right) - snapped_left,
+        snap(bottom) - snapped_top,
+    );
+
+    if !descend {
+        return;
+    }
+
+    for child in node.children(tree).filter(|child| child.visible(store)) {
+        snap_to_pixels(child, (left, top), (snapped_left, snapped_top), scale_factor, cache, tree, store);
+    }
+}
+
+/// Reports the index of `node` and each of its visible descendants in the paint order to the cache.
+///
+/// The nodes of a subtree skipped by the passes after layout are painted one after another if the subtree forms its
+/// own stacking context or contains none. Their indices are not reported again if the subtree starts at the same index
+/// as before.
+pub(crate) fn stacking_order<N, C>(node: &N, cache: &mut C, tree: &<N as Node>::Tree, store: &<N as Node>::Store)
+where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    paint_node(node, &mut 0, cache, tree, store);
+}
+
+/// Returns the number of nodes in the subtree of `node` and the index it was painted at last time, if the subtree is
+/// skipped by the passes after layout and is painted one after another.
+fn skipped_paint<N, C>(node: &N, cache: &C, store: &<N as Node>::Store) -> Option<(usize, Option<usize>)>
+where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let last = cache.skipped(node)?;
+    let summary = last.summary?;
+    (node.z_index(store).is_some() || !summary.z_index).then_some((summary.nodes, last.paint_index))
+}
+
+/// Reports the indices of `node` and its visible descendants in the paint order as described for
+/// [`visit_paint_order`], starting from `index`.
+fn paint_node<N, C>(node: &N, index: &mut usize, cache: &mut C, tree: &<N as Node>::Tree, store: &<N as Node>::Store)
+where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let start = *index;
+    cache.set_stacking_order(node, start);
+    cache.set_paint_index(node, start);
+    *index += 1;
+
+    if let Some((nodes, last)) = skipped_paint(node, cache, store) {
+        if last == Some(start) {
+            *index += nodes - 1;
+            return;
+        }
+    }
+
+    // Skipped subtrees which are painted one after another are painted as a whole.
+    let collapsed = |child: &N| skipped_paint(child, cache, store).is_some();
+
+    let mut flow = Vec::new();
+    let mut contexts = Vec::new();
+    collect_stacking_context(node, tree, store, &|child| !collapsed(child), &mut flow, &mut contexts);
+    let flow = flow.into_iter().map(|child| (collapsed(child), child)).collect::<Vec<_>>();
+
+    contexts.sort_by_key(|(z_index, _)| *z_index);
+    let split = contexts.partition_point(|(z_index, _)| *z_index < 0);
+
+    for (_, context) in &contexts[..split] {
+        paint_node(*context, index, cache, tree, store);
+    }
+
+    for (collapsed, child) in flow {
+        if collapsed {
+            paint_node(child, index, cache, tree, store);
+        } else {
+            cache.set_stacking_order(child, *index);
+            cache.set_paint_index(child, *index);
+            *index += 1;
+        }
+    }
+
+    for (_, context) in &contexts[split..] {
+        paint_node(*context, index, cache, tree, store);
+    }
+}
+
+/// Returns the transform from the coordinate space of `node` to the coordinate space of its parent.
+///
+/// This is a translation by the position of the node, preceded by the [`transform`](Node::transform) of the node
+/// applied about its center.
+pub(crate) fn local_transform<N, C>(node: &N, cache: &C, store: &<N as Node>::Store) -> Transform
+where
+    N: Node,
+    C: Cache<Node = N>,
+{
+    let translate = Transform::translate(cache.posx(node), cache.posy(node));
+
+    match node.transform(store) {
+        Some(transform) => {
+            let (center_x, center_y) = (cache.width(node) / 2.0, cache.height(node) / 2.0);
+
+            Transform::translate(-center_x, -center_y)
+                .then(&transform)
+                .then(&Transform::translate(center_x, center_y))
+                .then(&translate)
+        }
+
+        None => translate,
+    }
+}
+
+/// Reports the window-space transform and bounds of `node` and its visible descendants to the cache, given the
+/// transform from the coordinate space of the parent of `node` to window space.
+pub(crate) fn window_bounds<N, C>(
+    node: &N,
+    parent: Transform,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+) where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let descend = visit_descendants(node, PassInput::Window { parent }, cache, tree, store);
+
+    let transform = local_transform(node, cache, store).then(&parent);
+    let (posx, posy, width, height) = transform.bounding_box(0.0, 0.0, cache.width(node), cache.height(node));
+
+    cache.set_transform(node, transform);
+    cache.set_window_bounds(node, posx, posy, width, height);
+
+    if !descend {
+        return;
+    }
+
+    for child in node.children(tree).filter(|child| child.visible(store)) {
+        window_bounds(child, transform, cache, tree, store);
+    }
+}
+
+/// The range of a clip on one axis, or `None` if the axis is not clipped.
+type ClipRange = Option<(Scalar, Scalar)>;
+
+/// Converts a clip from the coordinate space of the parent of a node into the coordinate space of the node, given the
+/// [`local_transform`] of the node.
+///
+/// Under a rotation or skew, the clip becomes the bounding box of the transformed clip rectangle, and a clip on only
+/// one axis is dropped. Nothing is visible under a transform which cannot be inverted, such as a zero scale, so the clip
+/// becomes empty.
+fn transform_clip(transform: Transform, clip_x: ClipRange, clip_y: ClipRange) -> (ClipRange, ClipRange) {
+    let Some(inverse) = transform.invert() else {
+        return (Some((0.0, 0.0)), Some((0.0, 0.0)));
+    };
+
+    if inverse.is_axis_aligned() {
+        let map = |(start, end): (Scalar, Scalar), scale: Scalar, offset: Scalar| {
+            let (start, end) = (start * scale + offset, end * scale + offset);
+            (start.min(end), start.max(end))
+        };
+
+        return (
+            clip_x.map(|clip| map(clip, inverse.a, inverse.e)),
+            clip_y.map(|clip| map(clip, inverse.d, inverse.f)),
+        );
+    }
+
+    match (clip_x, clip_y) {
+        (Some((left, right)), Some((top, bottom))) => {
+            let (posx, posy, width, height) = inverse.bounding_box(left, top, right - left, bottom - top);
+            (Some((posx, posx + width)), Some((posy, posy + height)))
+        }
+
+        _ => (None, None),
+    }
+}
+
+/// Returns the intersection of the range from `start` to `end` with an optional clip range.
+fn clip_range(start: Scalar, end: Scalar, clip: Option<(Scalar, Scalar)>) -> (Scalar, Scalar) {
+    match clip {
+        Some((clip_start, clip_end)) => {
+            let start = start.max(clip_start);
+            (start, end.min(clip_end).max(start))
+        }
+        None => (start, end),
+    }
+}
+
+/// Computes the clip rectangle of `node` and its descendants once layout is complete.
+///
+/// The clip rectangle of a node is the part of its bounds which remains visible after clipping by the overflow of
+/// its ancestors, in the same coordinate space as its bounds. `clip_x` and `clip_y` are the ranges the ancestors of
+/// the node clip it to, in the coordinate space of its parent, with `None` for an axis which is not clipped.
+pub(crate) fn layout_clip<N, C>(
+    node: &N,
+    clip_x: Option<(Scalar, Scalar)>,
+    clip_y: Option<(Scalar, Scalar)>,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+    ctx: &UnitContext,
+) where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    let descend = visit_descendants(node, PassInput::Clip { clip_x, clip_y, ctx: *ctx }, cache, tree, store);
+
+    let (width, height) = (cache.width(node), cache.height(node));
+
+    // The clip of a transformed node is taken from the bounding box of its transformed bounds.
+    let transform = local_transform(node, cache, store);
+    let (posx, posy, bounds_width, bounds_height) = transform.bounding_box(0.0, 0.0, width, height);
+
+    let (left, right) = clip_range(posx, posx + bounds_width, clip_x);
+    let (top, bottom) = clip_range(posy, posy + bounds_height, clip_y);
+    cache.set_clip(node, left, top, right - left, bottom - top);
+
+    // Convert the clip of the ancestors into the coordinate space of the node.
+    let (mut clip_x, mut clip_y) = transform_clip(transform, clip_x, clip_y);
+
+    let ctx = &UnitContext { width: Some(width), height: Some(height), ..node.unit_context(store, ctx) };
+
+    // A node which clips its content on an axis clips its children to its padding box.
+    let padding = padding_box(node, width, height, cache, store, ctx);
+
+    if node.horizontal_overflow(store).unwrap_or_default().is_clipped() {
+        clip_x = Some(clip_range(padding.x, padding.x + padding.width, clip_x));
+    }
+
+    if node.vertical_overflow(store).unwrap_or_default().is_clipped() {
+        clip_y = Some(clip_range(padding.y, padding.y + padding.height, clip_y));
+    }
+
+    if !descend {
+        return;
+    }
+
+    for child in node.children(tree).filter(|child| child.visible(store)) {
+        // Fixed nodes are positioned against the root and so are not clipped by their ancestors.
+        if child.position_type(store).unwrap_or_default() == PositionType::Fixed {
+            layout_clip(child, None, None, cache, tree, store, ctx);
+        } else {
+            layout_clip(child, clip_x, clip_y, cache, tree, store, ctx);
+        }
+    }
+}
+
+/// The kind of intrinsic size computed for a subtree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntrinsicSize {
+    MinContent,
+    MaxContent,
+}
+
+/// Computes the min-content or max-content size of a node on the main axis of `axis`, which is either
+/// [`LayoutType::Row`] for the width or [`LayoutType::Column`] for the height.
+///
+/// Pixel sizes are used as-is, while all other sizes contribute the intrinsic size of the content of the node.
+#[allow(clippy::too_many_arguments)]
+fn intrinsic_main<N, C>(
+    node: &N,
+    axis: LayoutType,
+    kind: IntrinsicSize,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+    sublayout: &mut <N as Node>::SubLayout<'_>,
+    ctx: &UnitContext,
+) -> Scalar
+where
+    N: Node,
+    C: Cache<Node = N>,
+{
+    let ctx = &node.unit_context(store, ctx);
+
+    let size = match node.main(store, axis, ctx) {
+        Pixels(val) => val,
+        MinContent => intrinsic_content_main(node, axis, IntrinsicSize::MinContent, cache, tree, store, sublayout, ctx),
+        MaxContent => intrinsic_content_main(node, axis, IntrinsicSize::MaxContent, cache, tree, store, sublayout, ctx),
+        _ => intrinsic_content_main(node, axis, kind, cache, tree, store, sublayout, ctx),
+    };
 
-    main_after: f32,
-    // Last parent constraints used to lay out this child.
-    last_layout_main: f32,
-    last_layout_cross: f32,
-    has_layout_constraints: bool,
+    // Only absolute constraints can be applied without a parent size.
+    let min = node.min_main(store, axis, ctx);
+    let max = node.max_main(store, axis, ctx);
+    let min = if min.is_pixels() { min.to_px(0.0, DEFAULT_MIN) } else { DEFAULT_MIN };
+    let max = if max.is_pixels() { max.to_px(0.0, DEFAULT_MAX) } else { DEFAULT_MAX };
+
+    size.min(max).max(min)
 }
 
-fn flip_alignment_horizontal(alignment: Alignment) -> Alignment {
-    match alignment {
-        Alignment::TopLeft => Alignment::TopRight,
-        Alignment::TopRight => Alignment::TopLeft,
-        Alignment::Left => Alignment::Right,
-        Alignment::Right => Alignment::Left,
-        Alignment::BottomLeft => Alignment::BottomRight,
-        Alignment::BottomRight => Alignment::BottomLeft,
-        alignment => alignment,
+/// Computes the min-content or max-content size of the content of a node on the main axis of `axis`.
+#[allow(clippy::too_many_arguments)]
+fn intrinsic_content_main<N, C>(
+    node: &N,
+    axis: LayoutType,
+    kind: IntrinsicSize,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+    sublayout: &mut <N as Node>::SubLayout<'_>,
+    ctx: &UnitContext,
+) -> Scalar
+where
+    N: Node,
+    C: Cache<Node = N>,
+{
+    let children = node
+        .children(tree)
+        .filter(|child| child.visible(store))
+        .filter(|child| child.position_type(store).unwrap_or_default().is_in_flow())
+        .collect::<SmallVec<[&N; 32]>>();
+
+    // Nodes without children are sized by their content, constrained to zero for the min-content size.
+    if children.is_empty() {
+        let p_main = match kind {
+            IntrinsicSize::MinContent => Some(0.0),
+            IntrinsicSize::MaxContent => None,
+        };
+
+        return node
+            .content_sizing(cache, store, sublayout, axis, p_main, None, ctx)
+            .map(|(main, _)| main)
+            .unwrap_or_default();
     }
-}
 
-#[inline]
-fn same_f32(a: f32, b: f32) -> bool {
-    a.to_bits() == b.to_bits()
+    let padding_border = node.padding_main_before(store, axis, ctx).to_px(0.0, 0.0)
+        + node.padding_main_after(store, axis, ctx).to_px(0.0, 0.0)
+        + node.border_main_before(store, axis, ctx).to_px(0.0, DEFAULT_BORDER_WIDTH)
+        + node.border_main_after(store, axis, ctx).to_px(0.0, DEFAULT_BORDER_WIDTH);
+
+    let gap = node.main_between(store, axis, ctx).to_px_clamped(
+        0.0,
+        0.0,
+        node.min_main_between(store, axis, ctx),
+        node.max_main_between(store, axis, ctx),
+    );
+
+    let layout_type = node.layout_type(store).unwrap_or_default();
+
+    let content = match layout_type {
+        LayoutType::Grid => {
+            let tracks = if axis == LayoutType::Row { node.grid_columns(store) } else { node.grid_rows(store) };
+            let tracks = tracks.unwrap_or_default().iter().map(|track| track.resolve(ctx)).collect::<Vec<_>>();
+
+            // Fixed tracks keep their size, while flexible tracks take the largest contribution of the
+            // children which start in that track and span only that track.
+            let mut track_sizes = tracks.iter().map(|track| track.to_px(0.0, 0.0)).collect::<Vec<_>>();
+            for child in children.iter() {
+                let (start, span) = if axis == LayoutType::Row {
+                    (child.column_start(store).unwrap_or_default(), child.column_span(store).unwrap_or(1))
+                } else {
+                    (child.row_start(store).unwrap_or_default(), child.row_span(store).unwrap_or(1))
+                };
+
+                if span == 1 && start < tracks.len() && !tracks[start].is_pixels() {
+                    let contribution = intrinsic_main(*child, axis, kind, cache, tree, store, sublayout, ctx);
+                    track_sizes[start] = track_sizes[start].max(contribution);
+                }
+            }
+
+            track_sizes.iter().sum::<Scalar>() + tracks.len().saturating_sub(1) as Scalar * gap
+        }
+
+        // Children are stacked along the axis, unless wrapping allows each child to sit on its own line.
+        layout_type
+            if layout_type == axis
+                && !(kind == IntrinsicSize::MinContent && node.wrap(store).unwrap_or_default() == LayoutWrap::Wrap) =>
+        {
+            let sum: Scalar = children
+                .iter()
+                .map(|child| {
+                    intrinsic_main(*child, axis, kind, cache, tree, store, sublayout, ctx)
+                        + intrinsic_margins(*child, axis, store, ctx)
+                })
+                .sum();
+            sum + (children.len() - 1) as Scalar * gap
+        }
+
+        LayoutType::Overlay => children
+            .iter()
+            .map(|child| intrinsic_main(*child, axis, kind, cache, tree, store, sublayout, ctx))
+            .fold(0.0, Scalar::max),
+
+        _ => children
+            .iter()
+            .map(|child| {
+                intrinsic_main(*child, axis, kind, cache, tree, store, sublayout, ctx)
+                    + intrinsic_margins(*child, axis, store, ctx)
+            })
+            .fold(0.0, Scalar::max),
+    };
+
+    content + padding_border
 }
 
-fn alignment_fractions(alignment: Alignment) -> (f32, f32) {
-    // Convert alignment into normalized horizontal/vertical fractions in [0, 1].
-    // These fractions are later multiplied by available free space.
-    match alignment {
-        Alignment::TopLeft => (0.0, 0.0),
-        Alignment::TopCenter => (0.5, 0.0),
-        Alignment::TopRight => (1.0, 0.0),
-        Alignment::Left => (0.0, 0.5),
-        Alignment::Center => (0.5, 0.5),
-        Alignment::Right => (1.0, 0.5),
-        Alignment::BottomLeft => (0.0, 1.0),
-        Alignment::BottomCenter => (0.5, 1.0),
-        Alignment::BottomRight => (1.0, 1.0),
+/// Returns the pixel spacing of a relative child of a stack or wrap layout on the main axis of `axis`.
+fn intrinsic_margins<N: Node>(child: &N, axis: LayoutType, store: &<N as Node>::Store, ctx: &UnitContext) -> Scalar {
+    if child.position_type(store).unwrap_or_default() == PositionType::Sticky {
+        return 0.0;
     }
+
+    [child.main_before(store, axis, ctx), child.main_after(store, axis, ctx)]
+        .iter()
+        .map(|margin| if margin.is_pixels() { margin.to_px(0.0, 0.0) } else { 0.0 })
+        .sum()
 }
 
-fn absolute_axis_position(before: Units, after: Units, parent_size: f32, child_size: f32) -> f32 {
-    // Resolve a child position on one axis from before/after offsets.
-    // This is shared by absolute positioning in stack and overlay layouts.
-    match (before, after) {
-        (Pixels(val), _) => val,
-        (Percentage(val), _) => val * 0.01 * parent_size,
-        (_, Pixels(val)) => parent_size - val - child_size,
-        (_, Percentage(val)) => parent_size - child_size - val * 0.01 * parent_size,
-        (Stretch(b), Stretch(a)) => {
-            if b == a {
-                (parent_size - child_size) * 0.5
-            } else {
-                (parent_size - child_size) * (b / (b + a))
-            }
+/// Converts intrinsic sizing keywords into pixels on the main axis of `parent_layout_type`.
+#[allow(clippy::too_many_arguments)]
+fn resolve_intrinsic<N, C>(
+    units: Units,
+    node: &N,
+    parent_layout_type: LayoutType,
+    available: Scalar,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+    sublayout: &mut <N as Node>::SubLayout<'_>,
+    ctx: &UnitContext,
+) -> Units
+where
+    N: Node,
+    C: Cache<Node = N>,
+{
+    let axis = if parent_layout_type == LayoutType::Column { LayoutType::Column } else { LayoutType::Row };
+
+    match units {
+        MinContent => {
+            Pixels(intrinsic_content_main(node, axis, IntrinsicSize::MinContent, cache, tree, store, sublayout, ctx))
         }
-        (Stretch(_), Auto) => parent_size - child_size,
-        (Auto, Stretch(_)) => 0.0,
-        (Auto, Auto) => 0.0,
+        MaxContent => {
+            Pixels(intrinsic_content_main(node, axis, IntrinsicSize::MaxContent, cache, tree, store, sublayout, ctx))
+        }
+        FitContent => {
+            let min_content =
+                intrinsic_content_main(node, axis, IntrinsicSize::MinContent, cache, tree, store, sublayout, ctx);
+            let max_content =
+                intrinsic_content_main(node, axis, IntrinsicSize::MaxContent, cache, tree, store, sublayout, ctx);
+            Pixels(available.min(max_content).max(min_content))
+        }
+        units => units,
     }
 }
 
@@ -117,16 +1562,17 @@ fn absolute_axis_position(before: Units, after: Units, parent_size: f32, child_s
 pub(crate) fn layout_overlay<N, C>(
     node: &N,
     parent_layout_type: LayoutType,
-    parent_main: f32,
-    parent_cross: f32,
+    parent_main: Scalar,
+    parent_cross: Scalar,
     cache: &mut C,
     tree: &<N as Node>::Tree,
     store: &<N as Node>::Store,
     sublayout: &mut <N as Node>::SubLayout<'_>,
+    ctx: &UnitContext,
 ) -> Size
 where
     N: Node,
-    C: Cache<Node = N>,
+    C: LayoutState<Node = N>,
 {
     // Interpret parent-provided main/cross as concrete width/height for overlay.
     // Use parent dimensions as the containing block for percentage-based constraints,
@@ -141,22 +1587,26 @@ where
     // Resolve this node's own size constraints in physical width/height axes.
     // Use containing block dimensions (parent) as the reference for percentage resolution,
     // not the computed size (which may be 0 during auto-sizing).
-    let width = node.width(store).unwrap_or(Stretch(1.0));
-    let height = node.height(store).unwrap_or(Stretch(1.0));
-    let mut min_width = node.min_width(store).unwrap_or(Pixels(0.0)).to_px(contain_width, DEFAULT_MIN);
-    let mut max_width = node.max_width(store).unwrap_or(Pixels(f32::MAX)).to_px(contain_width, DEFAULT_MAX);
-    let mut min_height = node.min_height(store).unwrap_or(Pixels(0.0)).to_px(contain_height, DEFAULT_MIN);
-    let mut max_height = node.max_height(store).unwrap_or(Pixels(f32::MAX)).to_px(contain_height, DEFAULT_MAX);
-
-    let border_left = node.border_left(store).unwrap_or_default().to_px(contain_width, DEFAULT_BORDER_WIDTH);
-    let border_right = node.border_right(store).unwrap_or_default().to_px(contain_width, DEFAULT_BORDER_WIDTH);
-    let border_top = node.border_top(store).unwrap_or_default().to_px(contain_height, DEFAULT_BORDER_WIDTH);
-    let border_bottom = node.border_bottom(store).unwrap_or_default().to_px(contain_height, DEFAULT_BORDER_WIDTH);
-
-    let padding_left = node.padding_left(store).unwrap_or_default().to_px(contain_width, 0.0);
-    let padding_right = node.padding_right(store).unwrap_or_default().to_px(contain_width, 0.0);
-    let padding_top = node.padding_top(store).unwrap_or_default().to_px(contain_height, 0.0);
-    let padding_bottom = node.padding_bottom(store).unwrap_or_default().to_px(contain_height, 0.0);
+    let width = node.main(store, LayoutType::Overlay, ctx);
+    let height = node.cross(store, LayoutType::Overlay, ctx);
+    let mut min_width = node.min_main(store, LayoutType::Overlay, ctx).to_px(contain_width, DEFAULT_MIN);
+    let mut max_width = node.max_main(store, LayoutType::Overlay, ctx).to_px(contain_width, DEFAULT_MAX);
+    let mut min_height = node.min_cross(store, LayoutType::Overlay, ctx).to_px(contain_height, DEFAULT_MIN);
+    let mut max_height = node.max_cross(store, LayoutType::Overlay, ctx).to_px(contain_height, DEFAULT_MAX);
+
+    let border_left =
+        node.border_main_before(store, LayoutType::Overlay, ctx).to_px(contain_width, DEFAULT_BORDER_WIDTH);
+    let border_right =
+        node.border_main_after(store, LayoutType::Overlay, ctx).to_px(contain_width, DEFAULT_BORDER_WIDTH);
+    let border_top =
+        node.border_cross_before(store, LayoutType::Overlay, ctx).to_px(contain_height, DEFAULT_BORDER_WIDTH);
+    let border_bottom =
+        node.border_cross_after(store, LayoutType::Overlay, ctx).to_px(contain_height, DEFAULT_BORDER_WIDTH);
+
+    let padding_left = node.padding_main_before(store, LayoutType::Overlay, ctx).to_px(contain_width, 0.0);
+    let padding_right = node.padding_main_after(store, LayoutType::Overlay, ctx).to_px(contain_width, 0.0);
+    let padding_top = node.padding_cross_before(store, LayoutType::Overlay, ctx).to_px(contain_height, 0.0);
+    let padding_bottom = node.padding_cross_after(store, LayoutType::Overlay, ctx).to_px(contain_height, 0.0);
 
     // Split visible children by position type; relative children participate in
     // overlay alignment, absolute children keep explicit edge-based positioning.
@@ -164,8 +1614,9 @@ where
     let mut absolute_children = SmallVec::<[&N; 8]>::new();
     for child in node.children(tree).filter(|child| child.visible(store)) {
         match child.position_type(store).unwrap_or_default() {
-            PositionType::Relative => relative_children.push(child),
+            PositionType::Relative | PositionType::Sticky => relative_children.push(child),
             PositionType::Absolute => absolute_children.push(child),
+            PositionType::Fixed => {}
         }
     }
 
@@ -185,30 +1636,28 @@ where
         let available_height = computed_height - padding_top - padding_bottom - border_top - border_bottom;
 
         for child in relative_children.iter().copied() {
-            let child_width = child.width(store).unwrap_or(Stretch(1.0));
-            let child_height = child.height(store).unwrap_or(Stretch(1.0));
+            let child_width = child.main(store, LayoutType::Overlay, ctx);
+            let child_height = child.cross(store, LayoutType::Overlay, ctx);
 
-            let child_min_width = child.min_width(store).unwrap_or(Pixels(0.0));
-            let child_max_width = child.max_width(store).unwrap_or(Pixels(f32::MAX));
-            let child_min_height = child.min_height(store).unwrap_or(Pixels(0.0));
-            let child_max_height = child.max_height(store).unwrap_or(Pixels(f32::MAX));
+            let child_min_width = child.min_main(store, LayoutType::Overlay, ctx);
+            let child_max_width = child.max_main(store, LayoutType::Overlay, ctx);
+            let child_min_height = child.min_cross(store, LayoutType::Overlay, ctx);
+            let child_max_height = child.max_cross(store, LayoutType::Overlay, ctx);
 
             // Stretch children are constrained directly to available space,
             // while non-stretch children are measured against that space.
             let target_width = if child_width.is_stretch() {
-                available_width.clamp(
-                    child_min_width.to_px(available_width, DEFAULT_MIN),
-                    child_max_width.to_px(available_width, DEFAULT_MAX),
-                )
+                available_width
+                    .min(child_max_width.to_px(available_width, DEFAULT_MAX))
+                    .max(child_min_width.to_px(available_width, DEFAULT_MIN))
             } else {
                 available_width
             };
 
             let target_height = if child_height.is_stretch() {
-                available_height.clamp(
-                    child_min_height.to_px(available_height, DEFAULT_MIN),
-                    child_max_height.to_px(available_height, DEFAULT_MAX),
-                )
+                available_height
+                    .min(child_max_height.to_px(available_height, DEFAULT_MAX))
+                    .max(child_min_height.to_px(available_height, DEFAULT_MIN))
             } else {
                 available_height
             };
@@ -216,13 +1665,15 @@ where
             // Overlay children recurse with Overlay parent semantics so descendants
             // inherit overlay axis behavior when needed.
             let child_size =
-                layout(child, LayoutType::Overlay, target_width, target_height, cache, tree, store, sublayout);
+                layout(child, LayoutType::Overlay, target_width, target_height, cache, tree, store, sublayout, ctx);
 
             children.push(ChildNode {
                 node: child,
                 cross: child_size.cross,
                 main: child_size.main,
+                main_before: 0.0,
                 main_after: 0.0,
+                main_between: 0.0,
                 last_layout_main: target_width,
                 last_layout_cross: target_height,
                 has_layout_constraints: true,
@@ -233,31 +1684,31 @@ where
             break;
         }
 
-        let max_child_width = children.iter().map(|child| child.main).reduce(f32::max).unwrap_or_default();
-        let max_child_height = children.iter().map(|child| child.cross).reduce(f32::max).unwrap_or_default();
+        let max_child_width = children.iter().map(|child| child.main).reduce(Scalar::max).unwrap_or_default();
+        let max_child_height = children.iter().map(|child| child.cross).reduce(Scalar::max).unwrap_or_default();
 
         // Auto-size in overlay is based on max extents (not sums), because
         // children can overlap and are independently aligned in the same box.
-        if width.is_auto() || node.min_width(store).unwrap_or(Pixels(0.0)).is_auto() {
+        if width.is_auto() || node.is_content_min_main(store, LayoutType::Overlay, ctx) {
             min_width = max_child_width + padding_left + padding_right + border_left + border_right;
         }
 
-        if node.max_width(store).unwrap_or(Pixels(f32::MAX)).is_auto() && max_child_width != 0.0 {
+        if node.max_main(store, LayoutType::Overlay, ctx).is_auto() && max_child_width != 0.0 {
             max_width = max_child_width + padding_left + padding_right + border_left + border_right;
         }
 
-        if height.is_auto() || node.min_height(store).unwrap_or(Pixels(0.0)).is_auto() {
+        if height.is_auto() || node.is_content_min_cross(store, LayoutType::Overlay, ctx) {
             min_height = max_child_height + padding_top + padding_bottom + border_top + border_bottom;
         }
 
-        if node.max_height(store).unwrap_or(Pixels(f32::MAX)).is_auto() && max_child_height != 0.0 {
+        if node.max_cross(store, LayoutType::Overlay, ctx).is_auto() && max_child_height != 0.0 {
             max_height = max_child_height + padding_top + padding_bottom + border_top + border_bottom;
         }
 
         let next_width = computed_width.max(min_width).min(max_width);
         let next_height = computed_height.max(min_height).min(max_height);
 
-        if same_f32(next_width, computed_width) && same_f32(next_height, computed_height) {
+        if same_scalar(next_width, computed_width) && same_scalar(next_height, computed_height) {
             break;
         }
 
@@ -282,17 +1733,8 @@ where
     let (align_x, align_y) = alignment_fractions(alignment);
 
     for child in &children {
-        let mut child_posx = align_x * (available_width - child.main);
-        let mut child_posy = align_y * (available_height - child.cross);
-
-        // Parent scroll offsets can override alignment-derived positions.
-        if let Some(scroll_x) = node.horizontal_scroll(store) {
-            child_posx = scroll_x;
-        }
-
-        if let Some(scroll_y) = node.vertical_scroll(store) {
-            child_posy = scroll_y;
-        }
+        let child_posx = align_x * (available_width - child.main);
+        let child_posy = align_y * (available_height - child.cross);
 
         cache.set_rect(
             child.node,
@@ -304,6 +1746,21 @@ where
         );
     }
 
+    let content = Bounds {
+        x: padding_left + border_left,
+        y: padding_top + border_top,
+        width: available_width,
+        height: available_height,
+    };
+    let padding = Bounds {
+        x: border_left,
+        y: border_top,
+        width: computed_width - border_left - border_right,
+        height: computed_height - border_top - border_bottom,
+    };
+
+    scroll_content(node, content, padding, cache, tree, store);
+
     // Absolute children are sized in the same box model used by stack/wrap:
     // padding box (content + padding), excluding border.
     let abs_width = computed_width - border_left - border_right;
@@ -317,43 +1774,44 @@ where
     for child in absolute_children.into_iter() {
         // Under RTL, left and right are logically swapped on the horizontal axis.
         let (child_leading, child_trailing) = if is_rtl {
-            (child.right(store).unwrap_or_default(), child.left(store).unwrap_or_default())
+            (child.main_after(store, LayoutType::Overlay, ctx), child.main_before(store, LayoutType::Overlay, ctx))
         } else {
-            (child.left(store).unwrap_or_default(), child.right(store).unwrap_or_default())
+            (child.main_before(store, LayoutType::Overlay, ctx), child.main_after(store, LayoutType::Overlay, ctx))
         };
 
         // Stretch sizing for absolute children consumes remaining axis size after offsets.
-        let child_width = if child.width(store).unwrap_or(Stretch(1.0)).is_stretch() {
-            let child_min_width = child.min_width(store).unwrap_or(Pixels(0.0)).to_px(abs_width, DEFAULT_MIN);
-            let child_max_width = child.max_width(store).unwrap_or(Pixels(f32::MAX)).to_px(abs_width, DEFAULT_MAX);
+        let child_width = if child.main(store, LayoutType::Overlay, ctx).is_stretch() {
+            let child_min_width = child.min_main(store, LayoutType::Overlay, ctx).to_px(abs_width, DEFAULT_MIN);
+            let child_max_width = child.max_main(store, LayoutType::Overlay, ctx).to_px(abs_width, DEFAULT_MAX);
             let leading_px = child_leading.to_px(abs_width, 0.0);
             let trailing_px = child_trailing.to_px(abs_width, 0.0);
 
-            abs_width.clamp(child_min_width, child_max_width) - leading_px - trailing_px
+            abs_width.min(child_max_width).max(child_min_width) - leading_px - trailing_px
         } else {
             abs_width
         };
 
-        let child_height = if child.height(store).unwrap_or(Stretch(1.0)).is_stretch() {
-            let child_min_height = child.min_height(store).unwrap_or(Pixels(0.0)).to_px(abs_height, DEFAULT_MIN);
-            let child_max_height = child.max_height(store).unwrap_or(Pixels(f32::MAX)).to_px(abs_height, DEFAULT_MAX);
-            let child_top = child.top(store).unwrap_or_default().to_px(abs_height, 0.0);
-            let child_bottom = child.bottom(store).unwrap_or_default().to_px(abs_height, 0.0);
+        let child_height = if child.cross(store, LayoutType::Overlay, ctx).is_stretch() {
+            let child_min_height = child.min_cross(store, LayoutType::Overlay, ctx).to_px(abs_height, DEFAULT_MIN);
+            let child_max_height = child.max_cross(store, LayoutType::Overlay, ctx).to_px(abs_height, DEFAULT_MAX);
+            let child_top = child.cross_before(store, LayoutType::Overlay, ctx).to_px(abs_height, 0.0);
+            let child_bottom = child.cross_after(store, LayoutType::Overlay, ctx).to_px(abs_height, 0.0);
 
-            abs_height.clamp(child_min_height, child_max_height) - child_top - child_bottom
+            abs_height.min(child_max_height).max(child_min_height) - child_top - child_bottom
         } else {
             abs_height
         };
 
         // Recurse first to resolve intrinsic/auto behavior under the resolved constraints.
-        let child_size = layout(child, LayoutType::Overlay, child_width, child_height, cache, tree, store, sublayout);
+        let child_size =
+            layout(child, LayoutType::Overlay, child_width, child_height, cache, tree, store, sublayout, ctx);
 
         // Then resolve explicit absolute offsets for final placement.
         // Horizontal axis respects RTL via the already-swapped leading/trailing values.
         let child_posx = absolute_axis_position(child_leading, child_trailing, abs_width, child_size.main);
         let child_posy = absolute_axis_position(
-            child.top(store).unwrap_or_default(),
-            child.bottom(store).unwrap_or_default(),
+            child.cross_before(store, LayoutType::Overlay, ctx),
+            child.cross_after(store, LayoutType::Overlay, ctx),
             abs_height,
             child_size.cross,
         );
@@ -381,16 +1839,17 @@ where
 pub(crate) fn layout_grid<N, C>(
     node: &N,
     parent_layout_type: LayoutType,
-    parent_main: f32,
-    parent_cross: f32,
+    parent_main: Scalar,
+    parent_cross: Scalar,
     cache: &mut C,
     tree: &<N as Node>::Tree,
     store: &<N as Node>::Store,
     sublayout: &mut <N as Node>::SubLayout<'_>,
+    ctx: &UnitContext,
 ) -> Size
 where
     N: Node,
-    C: Cache<Node = N>,
+    C: LayoutState<Node = N>,
 {
     let computed_main = parent_main;
     let computed_cross = parent_cross;
@@ -400,32 +1859,26 @@ where
         LayoutType::Row | LayoutType::Overlay | LayoutType::Grid => (parent_main, parent_cross),
     };
 
-    let padding_left = node.padding_left(store).unwrap_or_default().to_px(parent_width, 0.0);
-    let padding_right = node.padding_right(store).unwrap_or_default().to_px(parent_width, 0.0);
-    let padding_top = node.padding_top(store).unwrap_or_default().to_px(parent_height, 0.0);
-    let padding_bottom = node.padding_bottom(store).unwrap_or_default().to_px(parent_height, 0.0);
+    let padding_left = node.padding_left(store).unwrap_or_default().resolve(ctx).to_px(parent_width, 0.0);
+    let padding_right = node.padding_right(store).unwrap_or_default().resolve(ctx).to_px(parent_width, 0.0);
+    let padding_top = node.padding_top(store).unwrap_or_default().resolve(ctx).to_px(parent_height, 0.0);
+    let padding_bottom = node.padding_bottom(store).unwrap_or_default().resolve(ctx).to_px(parent_height, 0.0);
 
     parent_width -= padding_left + padding_right;
     parent_height -= padding_top + padding_bottom;
 
-    let grid_cols = node.grid_columns(store).unwrap_or_default();
-    let grid_rows = node.grid_rows(store).unwrap_or_default();
+    let grid_cols = node.grid_columns(store).unwrap_or_default().iter().map(|col| col.resolve(ctx)).collect::<Vec<_>>();
+    let grid_rows = node.grid_rows(store).unwrap_or_default().iter().map(|row| row.resolve(ctx)).collect::<Vec<_>>();
 
     let mut computed_grid_cols = vec![0.0; 2 * grid_cols.len() + 2];
     let mut computed_grid_rows = vec![0.0; 2 * grid_rows.len() + 2];
 
-    let horizontal_gap = node.horizontal_gap(store).unwrap_or_default();
-    let vertical_gap = node.vertical_gap(store).unwrap_or_default();
-
-    // Sum of all space and size flex factors on the col-axis of the node.
-    let mut col_flex_sum = 0.0;
+    let horizontal_gap = node.horizontal_gap(store).unwrap_or_default().resolve(ctx);
+    let vertical_gap = node.vertical_gap(store).unwrap_or_default().resolve(ctx);
 
     // List of stretch nodes for the col axis.
     let mut col_axis = SmallVec::<[StretchItem; 32]>::new();
 
-    // Sum of all space and size flex factors on the row-axis of the node.
-    let mut row_flex_sum = 0.0;
-
     // List of stretch nodes for the row axis.
     let mut row_axis = SmallVec::<[StretchItem; 32]>::new();
 
@@ -434,7 +1887,6 @@ where
         computed_grid_cols[idx] = col.to_px(parent_width, 0.0);
 
         if let Stretch(val) = col {
-            col_flex_sum += val;
             col_axis.push(StretchItem::new(idx, *val, ItemType::Size, 0.0, 1000.0));
         }
 
@@ -443,7 +1895,6 @@ where
             computed_grid_cols[gutter_idx] = horizontal_gap.to_px(parent_width, 0.0);
 
             if let Stretch(val) = horizontal_gap {
-                col_flex_sum += val;
                 col_axis.push(StretchItem::new(gutter_idx, val, ItemType::Size, 0.0, 1000.0));
             }
         }
@@ -454,7 +1905,6 @@ where
         computed_grid_rows[idx] = row.to_px(parent_height, 0.0);
 
         if let Stretch(val) = row {
-            row_flex_sum += val;
             row_axis.push(StretchItem::new(idx, *val, ItemType::Size, 0.0, 1000.0));
         }
 
@@ -463,14 +1913,13 @@ where
             computed_grid_rows[gutter_idx] = vertical_gap.to_px(parent_height, 0.0);
 
             if let Stretch(val) = vertical_gap {
-                row_flex_sum += val;
                 row_axis.push(StretchItem::new(gutter_idx, val, ItemType::Size, 0.0, 1000.0));
             }
         }
     }
 
-    let mut width_sum: f32 = computed_grid_cols.iter().sum();
-    let mut height_sum: f32 = computed_grid_rows.iter().sum();
+    let mut width_sum: Scalar = computed_grid_cols.iter().sum();
+    let mut height_sum: Scalar = computed_grid_rows.iter().sum();
 
     if !col_axis.is_empty() {
         loop {
@@ -484,9 +1933,13 @@ where
 
             let mut total_violation = 0.0;
 
-            for item in col_axis.iter_mut().filter(|item| !item.frozen) {
-                let actual_main = (item.factor * free_col_space / col_flex_sum).round();
+            let shares = distribute_stretch(
+                col_axis.iter().filter(|item| !item.frozen).map(|item| item.factor),
+                free_col_space,
+                ctx.numeric_mode,
+            );
 
+            for (item, actual_main) in col_axis.iter_mut().filter(|item| !item.frozen).zip(shares) {
                 let clamped = actual_main.min(item.max).max(item.min);
                 item.violation = clamped - actual_main;
                 total_violation += item.violation;
@@ -504,7 +1957,6 @@ where
 
                 // If the item is frozen, adjust the used_space and sum of cross stretch factors.
                 if item.frozen {
-                    col_flex_sum -= item.factor;
                     let prev = computed_grid_cols[item.index];
                     computed_grid_cols[item.index] = item.computed;
                     width_sum += item.computed - prev;
@@ -525,9 +1977,13 @@ where
 
             let mut total_violation = 0.0;
 
-            for item in row_axis.iter_mut().filter(|item| !item.frozen) {
-                let actual_main = (item.factor * free_row_space / row_flex_sum).round();
+            let shares = distribute_stretch(
+                row_axis.iter().filter(|item| !item.frozen).map(|item| item.factor),
+                free_row_space,
+                ctx.numeric_mode,
+            );
 
+            for (item, actual_main) in row_axis.iter_mut().filter(|item| !item.frozen).zip(shares) {
                 let clamped = actual_main.min(item.max).max(item.min);
                 item.violation = clamped - actual_main;
                 total_violation += item.violation;
@@ -545,7 +2001,6 @@ where
 
                 // If the item is frozen, adjust the used_space and sum of cross stretch factors.
                 if item.frozen {
-                    row_flex_sum -= item.factor;
                     let prev = computed_grid_rows[item.index];
                     computed_grid_rows[item.index] = item.computed;
                     height_sum += item.computed - prev;
@@ -594,7 +2049,7 @@ where
     let node_children = node
         .children(tree)
         .filter(|child| child.visible(store))
-        .filter(|child| child.position_type(store).unwrap_or_default() == PositionType::Relative);
+        .filter(|child| child.position_type(store).unwrap_or_default().is_in_flow());
 
     // Compute space and size of non-flexible relative children.
     for child in node_children {
@@ -612,7 +2067,7 @@ where
         let posy = computed_grid_rows[row_start];
         let height = computed_grid_rows[row_end] - posy;
 
-        layout(child, LayoutType::Row, width, height, cache, tree, store, sublayout);
+        layout(child, LayoutType::Row, width, height, cache, tree, store, sublayout, ctx);
 
         cache.set_rect(
             child,
@@ -624,6 +2079,16 @@ where
         );
     }
 
+    let content = Bounds { x: padding_left, y: padding_top, width: parent_width, height: parent_height };
+    let padding = Bounds {
+        x: 0.0,
+        y: 0.0,
+        width: parent_width + padding_left + padding_right,
+        height: parent_height + padding_top + padding_bottom,
+    };
+
+    scroll_content(node, content, padding, cache, tree, store);
+
     Size { main: computed_main, cross: computed_cross }
 }
 
@@ -635,16 +2100,17 @@ where
 pub(crate) fn layout_wrap<N, C>(
     node: &N,
     parent_layout_type: LayoutType,
-    parent_main: f32,
-    parent_cross: f32,
+    parent_main: Scalar,
+    parent_cross: Scalar,
     cache: &mut C,
     tree: &<N as Node>::Tree,
     store: &<N as Node>::Store,
     sublayout: &mut <N as Node>::SubLayout<'_>,
+    ctx: &UnitContext,
 ) -> Size
 where
     N: Node,
-    C: Cache<Node = N>,
+    C: LayoutState<Node = N>,
 {
     let layout_type = node.layout_type(store).unwrap_or_default();
 
@@ -653,15 +2119,16 @@ where
     let (parent_main, parent_cross) =
         if parent_layout_type == layout_type { (parent_main, parent_cross) } else { (parent_cross, parent_main) };
 
-    let border_main_before = node.border_main_before(store, layout_type).to_px(parent_main, DEFAULT_BORDER_WIDTH);
-    let border_main_after = node.border_main_after(store, layout_type).to_px(parent_main, DEFAULT_BORDER_WIDTH);
-    let border_cross_before = node.border_cross_before(store, layout_type).to_px(parent_cross, DEFAULT_BORDER_WIDTH);
-    let border_cross_after = node.border_cross_after(store, layout_type).to_px(parent_cross, DEFAULT_BORDER_WIDTH);
+    let border_main_before = node.border_main_before(store, layout_type, ctx).to_px(parent_main, DEFAULT_BORDER_WIDTH);
+    let border_main_after = node.border_main_after(store, layout_type, ctx).to_px(parent_main, DEFAULT_BORDER_WIDTH);
+    let border_cross_before =
+        node.border_cross_before(store, layout_type, ctx).to_px(parent_cross, DEFAULT_BORDER_WIDTH);
+    let border_cross_after = node.border_cross_after(store, layout_type, ctx).to_px(parent_cross, DEFAULT_BORDER_WIDTH);
 
-    let padding_main_before = node.padding_main_before(store, layout_type).to_px(parent_main, 0.0);
-    let padding_main_after = node.padding_main_after(store, layout_type).to_px(parent_main, 0.0);
-    let padding_cross_before = node.padding_cross_before(store, layout_type).to_px(parent_cross, 0.0);
-    let padding_cross_after = node.padding_cross_after(store, layout_type).to_px(parent_cross, 0.0);
+    let padding_main_before = node.padding_main_before(store, layout_type, ctx).to_px(parent_main, 0.0);
+    let padding_main_after = node.padding_main_after(store, layout_type, ctx).to_px(parent_main, 0.0);
+    let padding_cross_before = node.padding_cross_before(store, layout_type, ctx).to_px(parent_cross, 0.0);
+    let padding_cross_after = node.padding_cross_after(store, layout_type, ctx).to_px(parent_cross, 0.0);
 
     // Available space for children after subtracting padding and border.
     let avail_main = parent_main - padding_main_before - padding_main_after - border_main_before - border_main_after;
@@ -669,13 +2136,13 @@ where
         parent_cross - padding_cross_before - padding_cross_after - border_cross_before - border_cross_after;
 
     // Gap between items within a line (on the main axis).
-    let min_main_between = node.min_main_between(store, layout_type);
-    let max_main_between = node.max_main_between(store, layout_type);
+    let min_main_between = node.min_main_between(store, layout_type, ctx);
+    let max_main_between = node.max_main_between(store, layout_type, ctx);
     let item_gap_px =
-        node.main_between(store, layout_type).to_px_clamped(avail_main, 0.0, min_main_between, max_main_between);
+        node.main_between(store, layout_type, ctx).to_px_clamped(avail_main, 0.0, min_main_between, max_main_between);
 
     // Gap between lines (on the cross axis).
-    let line_gap_px = node.cross_between(store, layout_type).to_px(avail_cross, 0.0);
+    let line_gap_px = node.cross_between(store, layout_type, ctx).to_px(avail_cross, 0.0);
 
     let is_inline_rtl = matches!(layout_type, LayoutType::Row | LayoutType::Column)
         && node.direction(store).unwrap_or_default() == Direction::RightToLeft;
@@ -683,34 +2150,41 @@ where
     let relative_children = node
         .children(tree)
         .filter(|c| c.visible(store))
-        .filter(|c| c.position_type(store).unwrap_or_default() == PositionType::Relative)
+        .filter(|c| c.position_type(store).unwrap_or_default().is_in_flow())
         .collect::<SmallVec<[&N; 32]>>();
 
     let num_rel = relative_children.len();
 
     // Per-item data used during layout.
     struct WrapItem {
-        main: f32,
-        cross: f32,
+        main: Scalar,
+        cross: Scalar,
+        /// Space before and after the item on the main axis.
+        main_before: Scalar,
+        main_after: Scalar,
+        margins: Margins,
         /// Non-zero when this item has Stretch units on the main axis.
-        stretch_main_factor: f32,
-        cross_is_stretch: bool,
-        min_main: f32,
-        max_main: f32,
-        min_cross: f32,
-        max_cross: f32,
+        stretch_main_factor: Scalar,
+        /// Non-zero when this item has Stretch units on the cross axis.
+        stretch_cross_factor: Scalar,
+        min_main: Scalar,
+        max_main: Scalar,
+        min_cross: Scalar,
+        max_cross: Scalar,
     }
 
     // Phase 1: Compute sizes for all relative children.
     // Stretch-main items are deferred; their sizes are resolved per-line in phase 3.
     let mut items: SmallVec<[WrapItem; 32]> = SmallVec::with_capacity(num_rel);
     for child in relative_children.iter() {
-        let child_main_units = child.main(store, layout_type);
-        let child_min_main = child.min_main(store, layout_type);
-        let child_max_main = child.max_main(store, layout_type);
-        let child_min_cross = child.min_cross(store, layout_type);
-        let child_max_cross = child.max_cross(store, layout_type);
-        let child_cross_is_stretch = child.cross(store, layout_type).is_stretch();
+        let child_main_units = child.main(store, layout_type, ctx);
+        let child_min_main = child.min_main(store, layout_type, ctx);
+        let child_max_main = child.max_main(store, layout_type, ctx);
+        let child_min_cross = child.min_cross(store, layout_type, ctx);
+        let child_max_cross = child.max_cross(store, layout_type, ctx);
+        let child_margins = Margins::new(*child, store, layout_type, is_inline_rtl, ctx);
+        let stretch_cross_factor =
+            if let Stretch(factor) = child.cross(store, layout_type, ctx) { factor } else { 0.0 };
 
         let min_main_px = child_min_main.to_px(avail_main, DEFAULT_MIN);
         let max_main_px = child_max_main.to_px(avail_main, DEFAULT_MAX);
@@ -723,20 +2197,26 @@ where
             items.push(WrapItem {
                 main: base,
                 cross: 0.0,
+                main_before: child_margins.main_before.fixed(avail_main),
+                main_after: child_margins.main_after.fixed(avail_main),
+                margins: child_margins,
                 stretch_main_factor: factor,
-                cross_is_stretch: child_cross_is_stretch,
+                stretch_cross_factor,
                 min_main: min_main_px,
                 max_main: max_main_px,
                 min_cross: min_cross_px,
                 max_cross: max_cross_px,
             });
         } else {
-            let size = layout(*child, layout_type, avail_main, avail_cross, cache, tree, store, sublayout);
+            let size = layout(*child, layout_type, avail_main, avail_cross, cache, tree, store, sublayout, ctx);
             items.push(WrapItem {
                 main: size.main,
                 cross: size.cross,
+                main_before: child_margins.main_before.fixed(avail_main),
+                main_after: child_margins.main_after.fixed(avail_main),
+                margins: child_margins,
                 stretch_main_factor: 0.0,
-                cross_is_stretch: child_cross_is_stretch,
+                stretch_cross_factor,
                 min_main: min_main_px,
                 max_main: max_main_px,
                 min_cross: min_cross_px,
@@ -752,12 +2232,12 @@ where
     let mut lines: SmallVec<[std::ops::Range<usize>; 8]> = SmallVec::new();
     if num_rel > 0 {
         let mut line_start = 0usize;
-        let mut line_main_used = 0.0f32;
+        let mut line_main_used: Scalar = 0.0;
         let mut items_in_line = 0usize;
 
         for i in 0..num_rel {
             // Stretch items contribute their min size to the line-break decision (0 if no min set).
-            let size_contribution = items[i].main;
+            let size_contribution = items[i].main_before + items[i].main + items[i].main_after;
             let gap_before = if items_in_line > 0 { item_gap_px } else { 0.0 };
             let projected = line_main_used + gap_before + size_contribution;
 
@@ -784,27 +2264,59 @@ where
             continue;
         }
 
-        let mut stretch_sum = 0.0f32;
-        let mut fixed_sum = 0.0f32;
+        let mut stretch_sum: Scalar = 0.0;
+        let mut fixed_sum: Scalar = 0.0;
         for i in start..end {
             if items[i].stretch_main_factor > 0.0 {
                 stretch_sum += items[i].stretch_main_factor;
             } else {
                 fixed_sum += items[i].main;
             }
+
+            // Stretch spacing shares the free space of the line with stretch sizes.
+            stretch_sum += items[i].margins.main_before.factor() + items[i].margins.main_after.factor();
+            fixed_sum += items[i].main_before + items[i].main_after;
         }
 
         if stretch_sum > 0.0 {
-            let gap_total = (count - 1) as f32 * item_gap_px;
+            let gap_total = (count - 1) as Scalar * item_gap_px;
             let free_main = (avail_main - fixed_sum - gap_total).max(0.0);
 
+            // The space before, size, and space after of each item, in order.
+            let shares = distribute_stretch(
+                items[start..end].iter().flat_map(|item| {
+                    [item.margins.main_before.factor(), item.stretch_main_factor, item.margins.main_after.factor()]
+                }),
+                free_main,
+                ctx.numeric_mode,
+            );
+
             for i in start..end {
+                let [before, allocated, after] = [0, 1, 2].map(|offset| shares[3 * (i - start) + offset]);
+
+                let margins = items[i].margins;
+                if margins.main_before.units.is_stretch() {
+                    items[i].main_before = margins.main_before.clamp(before, avail_main);
+                }
+
+                if margins.main_after.units.is_stretch() {
+                    items[i].main_after = margins.main_after.clamp(after, avail_main);
+                }
+
                 let factor = items[i].stretch_main_factor;
                 if factor > 0.0 {
-                    let allocated = (factor / stretch_sum * free_main).round();
-                    let clamped = allocated.clamp(items[i].min_main, items[i].max_main);
-                    let size =
-                        layout(relative_children[i], layout_type, clamped, avail_cross, cache, tree, store, sublayout);
+                    let clamped = allocated.min(items[i].max_main).max(items[i].min_main);
+                    let size = layout(
+                        relative_children[i],
+                        layout_type,
+                        clamped,
+                        avail_cross,
+                        cache,
+                        tree,
+                        store,
+                        sublayout,
+                        ctx,
+                    );
                     items[i].main = size.main;
                     items[i].cross = size.cross;
                 }
@@ -813,14 +2325,14 @@ where
     }
 
     // Phase 4: Compute the cross extent of each line from non-cross-stretch children.
-    let mut line_cross: SmallVec<[f32; 8]> = SmallVec::with_capacity(lines.len());
+    let mut line_cross: SmallVec<[Scalar; 8]> = SmallVec::with_capacity(lines.len());
     for line in lines.iter() {
         let start = line.start;
         let end = line.end;
-        let mut max_cross = 0.0f32;
+        let mut max_cross: Scalar = 0.0;
         for i in start..end {
-            if !items[i].cross_is_stretch {
-                max_cross = max_cross.max(items[i].cross);
+            if items[i].stretch_cross_factor == 0.0 {
+                max_cross = max_cross.max(items[i].cross + items[i].margins.fixed_cross(avail_cross));
             }
         }
         line_cross.push(max_cross);
@@ -832,18 +2344,22 @@ where
         let end = line.end;
         let lc = line_cross[line_idx];
         for i in start..end {
-            if items[i].cross_is_stretch {
+            if items[i].stretch_cross_factor > 0.0 {
                 let child = relative_children[i];
-                let clamped_cross = lc.clamp(items[i].min_cross, items[i].max_cross);
-                let size = layout(child, layout_type, items[i].main, clamped_cross, cache, tree, store, sublayout);
+                let clamped_cross = items[i]
+                    .margins
+                    .stretch_cross(lc, items[i].stretch_cross_factor)
+                    .min(items[i].max_cross)
+                    .max(items[i].min_cross);
+                let size = layout(child, layout_type, items[i].main, clamped_cross, cache, tree, store, sublayout, ctx);
                 items[i].main = size.main;
                 items[i].cross = size.cross;
             }
         }
         // Re-compute line cross to include cross-stretch items in case they changed.
-        let mut max_cross = 0.0f32;
+        let mut max_cross: Scalar = 0.0;
         for i in start..end {
-            max_cross = max_cross.max(items[i].cross);
+            max_cross = max_cross.max(items[i].cross + items[i].margins.fixed_cross(avail_cross));
         }
         line_cross[line_idx] = max_cross;
     }
@@ -851,38 +2367,38 @@ where
     // Phase 6: Determine the final cross size of the container.
     let num_lines = lines.len();
     let total_content_cross = if num_lines > 0 {
-        line_cross.iter().sum::<f32>() + (num_lines.saturating_sub(1)) as f32 * line_gap_px
+        line_cross.iter().sum::<Scalar>() + (num_lines.saturating_sub(1)) as Scalar * line_gap_px
     } else {
         0.0
     };
 
-    let cross_units = node.cross(store, layout_type);
+    let cross_units = node.cross(store, layout_type, ctx);
     let final_cross = if cross_units.is_auto() || parent_cross == 0.0 {
         let raw =
             total_content_cross + padding_cross_before + padding_cross_after + border_cross_before + border_cross_after;
-        let min_c = node.min_cross(store, layout_type).to_px(0.0, DEFAULT_MIN);
-        let max_c = node.max_cross(store, layout_type).to_px(0.0, DEFAULT_MAX);
+        let min_c = node.min_cross(store, layout_type, ctx).to_px(0.0, DEFAULT_MIN);
+        let max_c = node.max_cross(store, layout_type, ctx).to_px(0.0, DEFAULT_MAX);
         raw.max(min_c).min(max_c)
     } else {
         parent_cross
     };
 
     // Recompute auto main size (for containers with Auto main axis).
-    let main_units = node.main(store, layout_type);
+    let main_units = node.main(store, layout_type, ctx);
     let final_main = if main_units.is_auto() || parent_main == 0.0 {
         let raw = lines
             .iter()
             .map(|line| {
-                let mut sum = 0.0f32;
+                let mut sum: Scalar = 0.0;
                 for i in line.start..line.end {
-                    sum += items[i].main;
+                    sum += items[i].main_before + items[i].main + items[i].main_after;
                 }
-                sum + (line.len().saturating_sub(1)) as f32 * item_gap_px
+                sum + (line.len().saturating_sub(1)) as Scalar * item_gap_px
             })
-            .fold(0.0f32, f32::max);
+            .fold(0.0, Scalar::max);
         let raw = raw + padding_main_before + padding_main_after + border_main_before + border_main_after;
-        let min_m = node.min_main(store, layout_type).to_px(0.0, DEFAULT_MIN);
-        let max_m = node.max_main(store, layout_type).to_px(0.0, DEFAULT_MAX);
+        let min_m = node.min_main(store, layout_type, ctx).to_px(0.0, DEFAULT_MIN);
+        let max_m = node.max_main(store, layout_type, ctx).to_px(0.0, DEFAULT_MAX);
         raw.max(min_m).min(max_m)
     } else {
         parent_main
@@ -900,32 +2416,34 @@ where
 
     let mut abs_items: SmallVec<[ChildNode<N>; 8]> = SmallVec::new();
     for child in abs_children {
-        let main = if child.main(store, layout_type).is_stretch() {
-            let child_min_main = child.min_main(store, layout_type).to_px(abs_avail_main, DEFAULT_MIN);
-            let child_max_main = child.max_main(store, layout_type).to_px(abs_avail_main, DEFAULT_MAX);
-            let main_before = child.main_before(store, layout_type).to_px(abs_avail_main, 0.0);
-            let main_after = child.main_after(store, layout_type).to_px(abs_avail_main, 0.0);
-            abs_avail_main.clamp(child_min_main, child_max_main) - main_before - main_after
+        let main = if child.main(store, layout_type, ctx).is_stretch() {
+            let child_min_main = child.min_main(store, layout_type, ctx).to_px(abs_avail_main, DEFAULT_MIN);
+            let child_max_main = child.max_main(store, layout_type, ctx).to_px(abs_avail_main, DEFAULT_MAX);
+            let main_before = child.main_before(store, layout_type, ctx).to_px(abs_avail_main, 0.0);
+            let main_after = child.main_after(store, layout_type, ctx).to_px(abs_avail_main, 0.0);
+            abs_avail_main.min(child_max_main).max(child_min_main) - main_before - main_after
         } else {
             abs_avail_main
         };
 
-        let cross = if child.cross(store, layout_type).is_stretch() {
-            let child_min_cross = child.min_cross(store, layout_type).to_px(abs_avail_cross, DEFAULT_MIN);
-            let child_max_cross = child.max_cross(store, layout_type).to_px(abs_avail_cross, DEFAULT_MAX);
-            let cross_before = child.cross_before(store, layout_type).to_px(abs_avail_cross, 0.0);
-            let cross_after = child.cross_after(store, layout_type).to_px(abs_avail_cross, 0.0);
-            abs_avail_cross.clamp(child_min_cross, child_max_cross) - cross_before - cross_after
+        let cross = if child.cross(store, layout_type, ctx).is_stretch() {
+            let child_min_cross = child.min_cross(store, layout_type, ctx).to_px(abs_avail_cross, DEFAULT_MIN);
+            let child_max_cross = child.max_cross(store, layout_type, ctx).to_px(abs_avail_cross, DEFAULT_MAX);
+            let cross_before = child.cross_before(store, layout_type, ctx).to_px(abs_avail_cross, 0.0);
+            let cross_after = child.cross_after(store, layout_type, ctx).to_px(abs_avail_cross, 0.0);
+            abs_avail_cross.min(child_max_cross).max(child_min_cross) - cross_before - cross_after
         } else {
             abs_avail_cross
         };
 
-        let size = layout(child, layout_type, main, cross, cache, tree, store, sublayout);
+        let size = layout(child, layout_type, main, cross, cache, tree, store, sublayout, ctx);
         abs_items.push(ChildNode {
             node: child,
             main: size.main,
             cross: size.cross,
+            main_before: 0.0,
             main_after: 0.0,
+            main_between: 0.0,
             last_layout_main: 0.0,
             last_layout_cross: 0.0,
             has_layout_constraints: false,
@@ -946,7 +2464,7 @@ where
     }
 
     let (mut main_align_frac, mut cross_align_frac) = match alignment {
-        Alignment::TopLeft => (0.0f32, 0.0f32),
+        Alignment::TopLeft => (0.0, 0.0),
         Alignment::TopCenter => (0.0, 0.5),
         Alignment::TopRight => (0.0, 1.0),
         Alignment::Left => (0.5, 0.0),
@@ -967,10 +2485,10 @@ where
         let end = line.end;
         let lc = line_cross[line_idx];
         let count = line.len();
-        let gap_total = (count.saturating_sub(1)) as f32 * item_gap_px;
-        let mut line_main_sum = 0.0f32;
+        let gap_total = (count.saturating_sub(1)) as Scalar * item_gap_px;
+        let mut line_main_sum: Scalar = 0.0;
         for i in start..end {
-            line_main_sum += items[i].main;
+            line_main_sum += items[i].main_before + items[i].main + items[i].main_after;
         }
         let free_main = (avail_main - line_main_sum - gap_total).max(0.0);
 
@@ -982,7 +2500,9 @@ where
             for (item_idx, i) in (start..end).rev().enumerate() {
                 let item = &items[i];
                 let child = relative_children[i];
-                let item_cross_offset = cross_align_frac * (lc - item.cross);
+                let item_cross_offset = item.margins.cross_position(lc, item.cross, cross_align_frac);
+
+                main_cursor += item.main_before;
 
                 cache.set_rect(
                     child,
@@ -993,7 +2513,7 @@ where
                     item.cross,
                 );
 
-                main_cursor += item.main;
+                main_cursor += item.main + item.main_after;
                 if item_idx + 1 < count {
                     main_cursor += item_gap_px;
                 }
@@ -1006,7 +2526,9 @@ where
                 let item = &items[i];
                 let child = relative_children[i];
 
-                let item_cross_offset = cross_align_frac * (lc - item.cross);
+                let item_cross_offset = item.margins.cross_position(lc, item.cross, cross_align_frac);
+
+                main_cursor += item.main_before;
 
                 cache.set_rect(
                     child,
@@ -1017,7 +2539,7 @@ where
                     item.cross,
                 );
 
-                main_cursor += item.main;
+                main_cursor += item.main + item.main_after;
                 if i + 1 < end {
                     main_cursor += item_gap_px;
                 }
@@ -1030,15 +2552,35 @@ where
         }
     }
 
+    // Content and padding boxes in main/cross axes.
+    let content = Bounds {
+        x: padding_main_before + border_main_before,
+        y: padding_cross_before + border_cross_before,
+        width: final_main - padding_main_before - padding_main_after - border_main_before - border_main_after,
+        height: final_cross - padding_cross_before - padding_cross_after - border_cross_before - border_cross_after,
+    };
+    let padding = Bounds {
+        x: border_main_before,
+        y: border_cross_before,
+        width: final_main - border_main_before - border_main_after,
+        height: final_cross - border_cross_before - border_cross_after,
+    };
+
+    if layout_type == LayoutType::Column {
+        scroll_content(node, content.transpose(), padding.transpose(), cache, tree, store);
+    } else {
+        scroll_content(node, content, padding, cache, tree, store);
+    }
+
     // Position absolute children.
     for abs_child in &abs_items {
         let (child_main_before, child_main_after) = if is_inline_rtl {
-            (abs_child.node.main_after(store, layout_type), abs_child.node.main_before(store, layout_type))
+            (abs_child.node.main_after(store, layout_type, ctx), abs_child.node.main_before(store, layout_type, ctx))
         } else {
-            (abs_child.node.main_before(store, layout_type), abs_child.node.main_after(store, layout_type))
+            (abs_child.node.main_before(store, layout_type, ctx), abs_child.node.main_after(store, layout_type, ctx))
         };
-        let child_cross_before = abs_child.node.cross_before(store, layout_type);
-        let child_cross_after = abs_child.node.cross_after(store, layout_type);
+        let child_cross_before = abs_child.node.cross_before(store, layout_type, ctx);
+        let child_cross_after = abs_child.node.cross_after(store, layout_type, ctx);
 
         let pma = abs_avail_main;
         let pca = abs_avail_cross;
@@ -1057,7 +2599,7 @@ where
             }
             (Stretch(_), Auto) => pma - abs_child.main,
             (Auto, Stretch(_)) => 0.0,
-            (Auto, Auto) => 0.0,
+            _ => 0.0,
         };
 
         let child_cross_pos = match (child_cross_before, child_cross_after) {
@@ -1074,7 +2616,7 @@ where
             }
             (Stretch(_), Auto) => pca - abs_child.cross,
             (Auto, Stretch(_)) => 0.0,
-            (Auto, Auto) => 0.0,
+            _ => 0.0,
         };
 
         cache.set_rect(
@@ -1109,82 +2651,199 @@ where
 /// * `tree` - A mutable reference to the [`Tree`](crate::Node::Tree).
 /// * `store` - A mutable reference to the [`Store`](crate::Node::Store).
 /// * `sublayout` - A mutable reference to the [`SubLayout`](crate::Node::SubLayout) context.
+/// * `ctx` - The [`UnitContext`] of the parent of the `node`, used to resolve viewport-relative and font-relative units.
 ///
 /// # Example
 ///
 /// ```
-/// layout(&root, LayoutType::Column, 600.0, 600.0, &mut cache, &tree, &store, &mut sublayout);
+/// layout(&root, LayoutType::Column, 600.0, 600.0, &mut cache, &tree, &store, &mut sublayout, &ctx);
 /// ```
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn layout<N, C>(
     node: &N,
     parent_layout_type: LayoutType,
-    parent_main: f32,
-    parent_cross: f32,
+    parent_main: Scalar,
+    parent_cross: Scalar,
     cache: &mut C,
     tree: &<N as Node>::Tree,
     store: &<N as Node>::Store,
     sublayout: &mut <N as Node>::SubLayout<'_>,
+    ctx: &UnitContext,
 ) -> Size
+where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    // A node which was last laid out with the same constraints keeps the results of that layout. Its descendants are
+    // left as they were after the passes which followed that layout, and are only restored from their records if those
+    // passes are given different inputs this time.
+    if let Some(record) = cache.layout_record(node) {
+        if record.parent_layout_type == parent_layout_type
+            && same_scalar(record.parent_main, parent_main)
+            && same_scalar(record.parent_cross, parent_cross)
+            && record.context == *ctx
+        {
+            cache.set_border(node, record.border);
+            return record.size;
+        }
+    }
+
+    let size = layout_node(node, parent_layout_type, parent_main, parent_cross, cache, tree, store, sublayout, ctx);
+    cache.set_laid_out(node);
+
+    // In fixed-point mode the size is rounded before it is used to position the node and its siblings.
+    let size = Size { main: ctx.numeric_mode.quantize(size.main), cross: ctx.numeric_mode.quantize(size.cross) };
+
+    // The bounds of each child are recorded as set by the layout of this node, before any adjustments made after
+    // layout, so that they can be restored if a later layout of this node is skipped.
+    for child in node.children(tree) {
+        record_bounds(child, cache);
+    }
+
+    let (posx, posy, width, height) = (cache.posx(node), cache.posy(node), cache.width(node), cache.height(node));
+    let context = *ctx;
+    let border = cache.border(node).unwrap_or_default();
+    cache.set_layout_record(
+        node,
+        LayoutRecord {
+            parent_layout_type,
+            parent_main,
+            parent_cross,
+            context,
+            size,
+            posx,
+            posy,
+            width,
+            height,
+            border,
+        },
+    );
+
+    size
+}
+
+/// Updates the bounds in the layout record of `node`, if it has one, to its current bounds.
+fn record_bounds<N, C>(node: &N, cache: &mut C)
 where
     N: Node,
     C: Cache<Node = N>,
 {
+    if let Some(record) = cache.layout_record(node) {
+        let (posx, posy, width, height) = (cache.posx(node), cache.posy(node), cache.width(node), cache.height(node));
+        let updated = LayoutRecord { posx, posy, width, height, ..record };
+        if updated != record {
+            cache.set_layout_record(node, updated);
+        }
+    }
+}
+
+/// Restores the bounds of the descendants of `node` from the records of their last layout, undoing any adjustments
+/// made to them after that layout, such as sticky positioning or pixel snapping. The passes after layout then visit
+/// the restored nodes as if they had been laid out again.
+fn restore_layout<N, C>(node: &N, cache: &mut C, tree: &<N as Node>::Tree, store: &<N as Node>::Store)
+where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    for child in node.children(tree).filter(|child| child.visible(store)) {
+        if let Some(record) = cache.layout_record(child) {
+            cache.set_bounds(child, record.posx, record.posy, record.width, record.height);
+            cache.set_border(child, record.border);
+            cache.set_laid_out(child);
+            restore_layout(child, cache, tree, store);
+        }
+    }
+}
+
+/// Performs layout on the given node and its children as described for [`layout`], returning the computed size of the
+/// node before it is rounded according to the [`NumericMode`].
+#[allow(clippy::too_many_arguments)]
+fn layout_node<N, C>(
+    node: &N,
+    parent_layout_type: LayoutType,
+    parent_main: Scalar,
+    parent_cross: Scalar,
+    cache: &mut C,
+    tree: &<N as Node>::Tree,
+    store: &<N as Node>::Store,
+    sublayout: &mut <N as Node>::SubLayout<'_>,
+    ctx: &UnitContext,
+) -> Size
+where
+    N: Node,
+    C: LayoutState<Node = N>,
+{
+    // The font size of the node is inherited by its children when resolving font-relative units.
+    let ctx = &node.unit_context(store, ctx);
+
     // The layout type of the node. Determines the main and cross axes of the children.
     let layout_type = node.layout_type(store).unwrap_or_default();
 
     // The desired main-axis and cross-axis sizes of the node.
-    let main = node.main(store, parent_layout_type);
-    let cross = node.cross(store, parent_layout_type);
+    // Intrinsic sizing keywords are converted to pixels up front, so the node is then sized as if it were fixed.
+    let cross_layout_type = if parent_layout_type == LayoutType::Column { LayoutType::Row } else { LayoutType::Column };
+    let main = node.main(store, parent_layout_type, ctx);
+    let main = resolve_intrinsic(main, node, parent_layout_type, parent_main, cache, tree, store, sublayout, ctx);
+    let cross = node.cross(store, parent_layout_type, ctx);
+    let cross = resolve_intrinsic(cross, node, cross_layout_type, parent_cross, cache, tree, store, sublayout, ctx);
 
     let mut min_main = if main.is_stretch() {
         DEFAULT_MIN
     } else {
-        node.min_main(store, parent_layout_type).to_px(parent_main, DEFAULT_MIN)
+        let min_main = node.min_main(store, parent_layout_type, ctx);
+        resolve_intrinsic(min_main, node, parent_layout_type, parent_main, cache, tree, store, sublayout, ctx)
+            .to_px(parent_main, DEFAULT_MIN)
     };
 
     let mut max_main = if main.is_stretch() {
         DEFAULT_MAX
     } else {
-        node.max_main(store, parent_layout_type).to_px(parent_main, DEFAULT_MAX)
+        let max_main = node.max_main(store, parent_layout_type, ctx);
+        resolve_intrinsic(max_main, node, parent_layout_type, parent_main, cache, tree, store, sublayout, ctx)
+            .to_px(parent_main, DEFAULT_MAX)
     };
 
-    let mut min_cross = node.min_cross(store, parent_layout_type).to_px(parent_cross, DEFAULT_MIN);
+    // The constraints of stretch sizes are applied by the parent, which gives the node its constrained size.
+    let mut min_cross = if cross.is_stretch() {
+        DEFAULT_MIN
+    } else {
+        let min_cross = node.min_cross(store, parent_layout_type, ctx);
+        resolve_intrinsic(min_cross, node, cross_layout_type, parent_cross, cache, tree, store, sublayout, ctx)
+            .to_px(parent_cross, DEFAULT_MIN)
+    };
 
-    let mut max_cross = node.max_cross(store, parent_layout_type).to_px(parent_cross, DEFAULT_MAX);
+    let mut max_cross = if cross.is_stretch() {
+        DEFAULT_MAX
+    } else {
+        let max_cross = node.max_cross(store, parent_layout_type, ctx);
+        resolve_intrinsic(max_cross, node, cross_layout_type, parent_cross, cache, tree, store, sublayout, ctx)
+            .to_px(parent_cross, DEFAULT_MAX)
+    };
 
     // Compute main-axis size.
     let mut computed_main = match main {
         Pixels(val) => val,
-        Percentage(val) => (parent_main * (val / 100.0)).round(),
+        Percentage(val) => ctx.numeric_mode.round(parent_main * (val / 100.0)),
         Stretch(_) => parent_main,
-        Auto => 0.0,
+        _ => 0.0,
     };
 
     // Compute cross-axis size.
     let mut computed_cross = match cross {
         Pixels(val) => val,
-        Percentage(val) => (parent_cross * (val / 100.0)).round(),
+        Percentage(val) => ctx.numeric_mode.round(parent_cross * (val / 100.0)),
         Stretch(_) => parent_cross,
-        Auto => 0.0,
+        _ => 0.0,
     };
 
-    let border_main_before =
-        node.border_main_before(store, parent_layout_type).to_px(computed_main, DEFAULT_BORDER_WIDTH);
-    let border_main_after =
-        node.border_main_after(store, parent_layout_type).to_px(computed_main, DEFAULT_BORDER_WIDTH);
-    let border_cross_before =
-        node.border_cross_before(store, parent_layout_type).to_px(computed_cross, DEFAULT_BORDER_WIDTH);
-    let border_cross_after =
-        node.border_cross_after(store, parent_layout_type).to_px(computed_cross, DEFAULT_BORDER_WIDTH);
-
     // Classify visible children once to avoid repeated tree traversals.
     let mut relative_children = SmallVec::<[&N; 32]>::new();
     let mut absolute_children = SmallVec::<[&N; 8]>::new();
     for child in node.children(tree).filter(|child| child.visible(store)) {
         match child.position_type(store).unwrap_or_default() {
-            PositionType::Relative => relative_children.push(child),
+            PositionType::Relative | PositionType::Sticky => relative_children.push(child),
             PositionType::Absolute => absolute_children.push(child),
+            PositionType::Fixed => {}
         }
     }
 
@@ -1195,13 +2854,15 @@ where
     let num_parent_directed_children = relative_children.len();
 
     // Apply content sizing.
-    if (node.min_main(store, parent_layout_type).is_auto() || node.min_cross(store, parent_layout_type).is_auto())
-        && num_parent_directed_children == 0
-    {
-        let p_main = if node.min_main(store, parent_layout_type).is_auto() { None } else { Some(computed_main) };
-        let p_cross = if node.min_cross(store, parent_layout_type).is_auto() { None } else { Some(computed_cross) };
-
-        if let Some(content_size) = node.content_sizing(store, sublayout, parent_layout_type, p_main, p_cross) {
+    let content_min_main = node.is_content_min_main(store, parent_layout_type, ctx);
+    let content_min_cross = node.is_content_min_cross(store, parent_layout_type, ctx);
+    if (content_min_main || content_min_cross) && num_parent_directed_children == 0 {
+        let p_main = if content_min_main { None } else { Some(computed_main) };
+        let p_cross = if content_min_cross { None } else { Some(computed_cross) };
+
+        if let Some(content_size) =
+            node.content_sizing(cache, store, sublayout, parent_layout_type, p_main, p_cross, ctx)
+        {
             min_main = content_size.0;
             min_cross = content_size.1;
         }
@@ -1211,7 +2872,9 @@ where
         let p_main = if main.is_auto() { None } else { Some(computed_main) };
         let p_cross = if cross.is_auto() { None } else { Some(computed_cross) };
 
-        if let Some(content_size) = node.content_sizing(store, sublayout, parent_layout_type, p_main, p_cross) {
+        if let Some(content_size) =
+            node.content_sizing(cache, store, sublayout, parent_layout_type, p_main, p_cross, ctx)
+        {
             computed_main = content_size.0;
             computed_cross = content_size.1;
         }
@@ -1220,16 +2883,80 @@ where
     computed_main = computed_main.max(min_main).min(max_main);
     computed_cross = computed_cross.max(min_cross).min(max_cross);
 
+    // Sizes relative to the other axis of the node are resolved once the referenced axis is known.
+    let main_is_width = parent_layout_type != LayoutType::Column;
+    if main.is_self_relative() && !cross.is_self_relative() {
+        let (width, height) = if main_is_width { (None, Some(computed_cross)) } else { (Some(computed_cross), None) };
+        computed_main = main.resolve(&UnitContext { width, height, ..*ctx }).to_px(parent_main, 0.0);
+        computed_main = computed_main.max(min_main).min(max_main);
+    } else if cross.is_self_relative() && !main.is_self_relative() {
+        let (width, height) = if main_is_width { (Some(computed_main), None) } else { (None, Some(computed_main)) };
+        computed_cross = cross.resolve(&UnitContext { width, height, ..*ctx }).to_px(parent_cross, 0.0);
+        computed_cross = computed_cross.max(min_cross).min(max_cross);
+    }
+
+    // Padding, border and gap of the node may be relative to its computed size.
+    let (width, height) = if main_is_width { (computed_main, computed_cross) } else { (computed_cross, computed_main) };
+    let ctx = &UnitContext { width: Some(width), height: Some(height), ..*ctx };
+
+    let border_main_before =
+        node.border_main_before(store, parent_layout_type, ctx).to_px(computed_main, DEFAULT_BORDER_WIDTH);
+    let border_main_after =
+        node.border_main_after(store, parent_layout_type, ctx).to_px(computed_main, DEFAULT_BORDER_WIDTH);
+    let border_cross_before =
+        node.border_cross_before(store, parent_layout_type, ctx).to_px(computed_cross, DEFAULT_BORDER_WIDTH);
+    let border_cross_after =
+        node.border_cross_after(store, parent_layout_type, ctx).to_px(computed_cross, DEFAULT_BORDER_WIDTH);
+
+    // The passes which run after layout use the same borders, which may differ from the borders resolved against the
+    // final size of the node if its size depends on its content.
+    let border = if main_is_width {
+        [border_main_before, border_cross_before, border_main_after, border_cross_after]
+    } else {
+        [border_cross_before, border_main_before, border_cross_after, border_main_after]
+    };
+    cache.set_border(node, border);
+
     if layout_type == LayoutType::Grid {
-        return layout_grid(node, parent_layout_type, computed_main, computed_cross, cache, tree, store, sublayout);
+        return layout_grid(
+            node,
+            parent_layout_type,
+            computed_main,
+            computed_cross,
+            cache,
+            tree,
+            store,
+            sublayout,
+            ctx,
+        );
     }
 
     if layout_type == LayoutType::Overlay {
-        return layout_overlay(node, parent_layout_type, computed_main, computed_cross, cache, tree, store, sublayout);
+        return layout_overlay(
+            node,
+            parent_layout_type,
+            computed_main,
+            computed_cross,
+            cache,
+            tree,
+            store,
+            sublayout,
+            ctx,
+        );
     }
 
     if node.wrap(store).unwrap_or_default() == LayoutWrap::Wrap {
-        return layout_wrap(node, parent_layout_type, computed_main, computed_cross, cache, tree, store, sublayout);
+        return layout_wrap(
+            node,
+            parent_layout_type,
+            computed_main,
+            computed_cross,
+            cache,
+            tree,
+            store,
+            sublayout,
+            ctx,
+        );
     }
 
     // Determine the parent_main/cross size to pass to the children based on the layout type of the parent and the node.
@@ -1240,9 +2967,6 @@ where
         (computed_cross, computed_main)
     };
 
-    // Sum of all space and size flex factors on the main-axis of the node.
-    let mut main_flex_sum = 0.0;
-
     // List of child nodes for the current node.
     let mut children = SmallVec::<[ChildNode<N>; 32]>::with_capacity(num_children);
 
@@ -1251,13 +2975,13 @@ where
     let mut main_axis = SmallVec::<[StretchItem; 32]>::new();
 
     // Parent overrides for child auto space.
-    let padding_main_before = node.padding_main_before(store, layout_type).to_px(parent_main, 0.0);
-    let padding_main_after = node.padding_main_after(store, layout_type).to_px(parent_main, 0.0);
-    let padding_cross_before = node.padding_cross_before(store, layout_type).to_px(parent_cross, 0.0);
-    let padding_cross_after = node.padding_cross_after(store, layout_type).to_px(parent_cross, 0.0);
+    let padding_main_before = node.padding_main_before(store, layout_type, ctx).to_px(parent_main, 0.0);
+    let padding_main_after = node.padding_main_after(store, layout_type, ctx).to_px(parent_main, 0.0);
+    let padding_cross_before = node.padding_cross_before(store, layout_type, ctx).to_px(parent_cross, 0.0);
+    let padding_cross_after = node.padding_cross_after(store, layout_type, ctx).to_px(parent_cross, 0.0);
 
-    let min_main_between = node.min_main_between(store, layout_type);
-    let max_main_between = node.max_main_between(store, layout_type);
+    let min_main_between = node.min_main_between(store, layout_type, ctx);
+    let max_main_between = node.max_main_between(store, layout_type, ctx);
 
     parent_main = parent_main - padding_main_before - padding_main_after - border_main_before - border_main_after;
     parent_cross = parent_cross - padding_cross_before - padding_cross_after - border_cross_before - border_cross_after;
@@ -1274,35 +2998,53 @@ where
 
     let last = relative_children.len().checked_sub(1);
 
+    // Spacing around each relative child, indexed in the same order as `children`.
+    let mut margins = SmallVec::<[Margins; 32]>::with_capacity(relative_children.len());
+
     // Compute space and size of non-flexible relative children.
     for (index, child) in relative_children.into_iter().enumerate() {
-        let child_main = child.main(store, layout_type);
-        let child_cross = child.cross(store, layout_type);
+        let child_main = child.main(store, layout_type, ctx);
+        let child_cross = child.cross(store, layout_type, ctx);
+        let child_margins = Margins::new(child, store, layout_type, is_rtl, ctx);
 
         // Get fixed-size constraints.
-        let child_min_main = child.min_main(store, layout_type);
-        let child_max_main = child.max_main(store, layout_type);
+        let child_min_main = child.min_main(store, layout_type, ctx);
+        let child_max_main = child.max_main(store, layout_type, ctx);
 
-        let child_min_cross = child.min_cross(store, layout_type);
-        let child_max_cross = child.max_cross(store, layout_type);
+        let child_min_cross = child.min_cross(store, layout_type, ctx);
+        let child_max_cross = child.max_cross(store, layout_type, ctx);
 
-        let mut computed_child_main_after = 0.0f32;
+        let mut computed_child_main_between: Scalar = 0.0;
 
         if last != Some(index) {
-            let child_main_after = node.main_between(store, layout_type);
+            let child_main_between = node.main_between(store, layout_type, ctx);
 
-            if let Stretch(factor) = child_main_after {
-                main_flex_sum += factor;
+            if let Stretch(factor) = child_main_between {
                 main_axis.push(StretchItem::new(
                     index,
                     factor,
-                    ItemType::After,
+                    ItemType::Between,
                     min_main_between.to_px(parent_main, DEFAULT_MIN),
                     max_main_between.to_px(parent_main, DEFAULT_MAX),
                 ));
             } else {
-                computed_child_main_after =
-                    child_main_after.to_px_clamped(parent_main, 0.0, min_main_between, max_main_between);
+                computed_child_main_between =
+                    child_main_between.to_px_clamped(parent_main, 0.0, min_main_between, max_main_between);
+            }
+        }
+
+        // Collect stretch main-axis spacing.
+        for (margin, item_type) in
+            [(child_margins.main_before, ItemType::Before), (child_margins.main_after, ItemType::After)]
+        {
+            if let Stretch(factor) = margin.units {
+                main_axis.push(StretchItem::new(
+                    index,
+                    factor,
+                    item_type,
+                    margin.min.to_px(parent_main, DEFAULT_MIN),
+                    margin.max.to_px(parent_main, DEFAULT_MAX),
+                ));
             }
         }
 
@@ -1310,7 +3052,6 @@ where
 
         // Collect stretch main items.
         if let Stretch(factor) = child_main {
-            main_flex_sum += factor;
             main_axis.push(StretchItem::new(
                 index,
                 factor,
@@ -1330,20 +3071,31 @@ where
         let mut last_layout_cross = 0.0;
 
         if !child_main.is_stretch() && (!child_cross.is_stretch() || child_min_cross.is_auto()) {
-            let child_size = layout(child, layout_type, parent_main, parent_cross, cache, tree, store, sublayout);
+            // A stretch cross size fills the cross space left over by the spacing of the child.
+            let target_cross = if let Stretch(factor) = child_cross {
+                child_margins.stretch_cross(parent_cross, factor)
+            } else {
+                parent_cross
+            };
+
+            let child_size = layout(child, layout_type, parent_main, target_cross, cache, tree, store, sublayout, ctx);
 
             computed_child_main = child_size.main;
             computed_child_cross = child_size.cross;
             has_layout_constraints = true;
             last_layout_main = parent_main;
-            last_layout_cross = parent_cross;
+            last_layout_cross = target_cross;
         }
 
+        margins.push(child_margins);
+
         children.push(ChildNode {
             node: child,
             cross: computed_child_cross,
             main: computed_child_main,
-            main_after: computed_child_main_after,
+            main_before: child_margins.main_before.fixed(parent_main),
+            main_after: child_margins.main_after.fixed(parent_main),
+            main_between: computed_child_main_between,
             last_layout_main,
             last_layout_cross,
             has_layout_constraints,
@@ -1351,14 +3103,19 @@ where
     }
 
     // Sum of all child nodes on the main-axis.
-    let mut main_sum: f32 = children.iter().map(|child| child.main + child.main_after).sum();
+    let mut main_sum: Scalar = children.iter().map(|child| child.main_total()).sum();
 
     // Maximum of all child nodes on the cross-axis.
-    let mut cross_max: f32 = children.iter().map(|child| child.cross).reduce(f32::max).unwrap_or_default();
+    let mut cross_max: Scalar = children
+        .iter()
+        .zip(margins.iter())
+        .map(|(child, margins)| child.cross + margins.fixed_cross(parent_cross))
+        .reduce(Scalar::max)
+        .unwrap_or_default();
 
     // Determine auto main and cross size from space and size of children.
     if num_parent_directed_children != 0 {
-        if main.is_auto() || node.min_main(store, parent_layout_type).is_auto() {
+        if main.is_auto() || node.is_content_min_main(store, parent_layout_type, ctx) {
             if parent_layout_type == layout_type {
                 min_main = main_sum + border_main_before + border_main_after + padding_main_before + padding_main_after;
             } else {
@@ -1367,7 +3124,7 @@ where
             }
         }
 
-        if node.max_main(store, parent_layout_type).is_auto() {
+        if node.max_main(store, parent_layout_type, ctx).is_auto() {
             if parent_layout_type == layout_type && main_sum != 0.0 {
                 max_main = main_sum + border_main_before + border_main_after + padding_main_before + padding_main_after;
             } else if cross_max != 0.0 {
@@ -1376,7 +3133,7 @@ where
             }
         }
 
-        if cross.is_auto() || node.min_cross(store, parent_layout_type).is_auto() {
+        if cross.is_auto() || node.is_content_min_cross(store, parent_layout_type, ctx) {
             if parent_layout_type == layout_type {
                 min_cross =
                     cross_max + border_cross_before + border_cross_after + padding_cross_before + padding_cross_after;
@@ -1386,7 +3143,7 @@ where
             }
         }
 
-        if node.max_cross(store, parent_layout_type).is_auto() {
+        if node.max_cross(store, parent_layout_type, ctx).is_auto() {
             if parent_layout_type == layout_type && cross_max != 0.0 {
                 max_cross =
                     cross_max + border_cross_before + border_cross_after + padding_cross_before + padding_cross_after;
@@ -1410,43 +3167,50 @@ where
     parent_cross = parent_cross - padding_cross_before - padding_cross_after - border_cross_before - border_cross_after;
 
     // Compute stretch size on the cross-axis for relative children.
-    for child in children
-        .iter_mut()
-        .filter(|child| child.node.position_type(store).unwrap_or_default() == PositionType::Relative)
-        .filter(|child| child.node.cross(store, layout_type).is_stretch())
-    {
-        if !child.node.main(store, layout_type).is_stretch() {
+    for (child, margins) in children.iter_mut().zip(margins.iter()) {
+        let Stretch(factor) = child.node.cross(store, layout_type, ctx) else {
+            continue;
+        };
+
+        let target_cross = margins.stretch_cross(parent_cross, factor);
+
+        if !child.node.main(store, layout_type, ctx).is_stretch() {
             if !child.has_layout_constraints
-                || !same_f32(child.last_layout_main, parent_main)
-                || !same_f32(child.last_layout_cross, parent_cross)
+                || !same_scalar(child.last_layout_main, parent_main)
+                || !same_scalar(child.last_layout_cross, target_cross)
             {
                 let child_size =
-                    layout(child.node, layout_type, parent_main, parent_cross, cache, tree, store, sublayout);
+                    layout(child.node, layout_type, parent_main, target_cross, cache, tree, store, sublayout, ctx);
                 child.main = child_size.main;
                 child.cross = child_size.cross;
                 child.last_layout_main = parent_main;
-                child.last_layout_cross = parent_cross;
+                child.last_layout_cross = target_cross;
                 child.has_layout_constraints = true;
             }
         } else {
-            let child_min_cross = if child.node.min_cross(store, layout_type).is_auto() {
+            let child_min_cross = if child.node.is_content_min_cross(store, layout_type, ctx) {
                 child.cross
             } else {
-                child.node.min_cross(store, layout_type).to_px(parent_cross, DEFAULT_MIN)
+                child.node.min_cross(store, layout_type, ctx).to_px(parent_cross, DEFAULT_MIN)
             };
 
-            let child_max_cross = child.node.max_cross(store, layout_type).to_px(parent_cross, DEFAULT_MAX);
+            let child_max_cross = child.node.max_cross(store, layout_type, ctx).to_px(parent_cross, DEFAULT_MAX);
 
-            child.cross = parent_cross.clamp(child_min_cross, child_max_cross);
+            child.cross = target_cross.min(child_max_cross).max(child_min_cross);
         }
     }
 
-    main_sum = children.iter().map(|child| child.main + child.main_after).sum();
-    cross_max = children.iter().map(|child| child.cross).reduce(f32::max).unwrap_or_default();
+    main_sum = children.iter().map(|child| child.main_total()).sum();
+    cross_max = children
+        .iter()
+        .zip(margins.iter())
+        .map(|(child, margins)| child.cross + margins.fixed_cross(parent_cross))
+        .reduce(Scalar::max)
+        .unwrap_or_default();
 
     // Determine auto main and cross size from space and size of children.
     if num_parent_directed_children != 0 {
-        if main.is_auto() || node.min_main(store, parent_layout_type).is_auto() {
+        if main.is_auto() || node.is_content_min_main(store, parent_layout_type, ctx) {
             if parent_layout_type == layout_type {
                 min_main = main_sum + border_main_before + border_main_after + padding_main_before + padding_main_after;
             } else {
@@ -1455,7 +3219,7 @@ where
             }
         }
 
-        if node.max_main(store, parent_layout_type).is_auto() {
+        if node.max_main(store, parent_layout_type, ctx).is_auto() {
             if parent_layout_type == layout_type && main_sum != 0.0 {
                 max_main = main_sum + border_main_before + border_main_after + padding_main_before + padding_main_after;
             } else if cross_max != 0.0 {
@@ -1464,7 +3228,7 @@ where
             }
         }
 
-        if cross.is_auto() || node.min_cross(store, parent_layout_type).is_auto() {
+        if cross.is_auto() || node.is_content_min_cross(store, parent_layout_type, ctx) {
             if parent_layout_type == layout_type {
                 min_cross =
                     cross_max + border_cross_before + border_cross_after + padding_cross_before + padding_cross_after;
@@ -1474,7 +3238,7 @@ where
             }
         }
 
-        if node.max_cross(store, parent_layout_type).is_auto() {
+        if node.max_cross(store, parent_layout_type, ctx).is_auto() {
             if parent_layout_type == layout_type && cross_max != 0.0 {
                 max_cross =
                     cross_max + border_cross_before + border_cross_after + padding_cross_before + padding_cross_after;
@@ -1501,22 +3265,36 @@ where
 
             let mut total_violation = 0.0;
 
-            for item in main_axis.iter_mut().filter(|item| !item.frozen) {
-                let input_main = (item.factor * free_main_space / main_flex_sum).round();
+            let shares = distribute_stretch(
+                main_axis.iter().filter(|item| !item.frozen).map(|item| item.factor),
+                free_main_space,
+                ctx.numeric_mode,
+            );
+
+            for (item, input_main) in main_axis.iter_mut().filter(|item| !item.frozen).zip(shares) {
                 let mut actual_main = input_main;
 
                 let child = &mut children[item.index];
 
                 if item.item_type == ItemType::Size {
                     let target_cross =
-                        if child.node.cross(store, layout_type).is_stretch() { child.cross } else { parent_cross };
+                        if child.node.cross(store, layout_type, ctx).is_stretch() { child.cross } else { parent_cross };
 
                     if !child.has_layout_constraints
-                        || !same_f32(child.last_layout_main, input_main)
-                        || !same_f32(child.last_layout_cross, target_cross)
+                        || !same_scalar(child.last_layout_main, input_main)
+                        || !same_scalar(child.last_layout_cross, target_cross)
                     {
-                        let child_size =
-                            layout(child.node, layout_type, input_main, target_cross, cache, tree, store, sublayout);
+                        let child_size = layout(
+                            child.node,
+                            layout_type,
+                            input_main,
+                            target_cross,
+                            cache,
+                            tree,
+                            store,
+                            sublayout,
+                            ctx,
+                        );
                         child.cross = child_size.cross;
                         actual_main = child_size.main;
                         item.measured = actual_main;
@@ -1527,7 +3305,7 @@ where
                         actual_main = item.measured;
                     }
 
-                    if child.node.min_main(store, layout_type).is_auto() {
+                    if child.node.is_content_min_main(store, layout_type, ctx) {
                         item.min = child.main;
                     }
                 }
@@ -1550,21 +3328,20 @@ where
 
                 // If the item is frozen, adjust the used_space and sum of cross stretch factors.
                 if item.frozen {
-                    main_flex_sum -= item.factor;
-                    let previous_total = child.main + child.main_after;
+                    let previous_total = child.main_total();
 
                     match item.item_type {
                         ItemType::Size => {
-                            if (item.computed - item.measured).abs() > f32::EPSILON {
-                                let target_cross = if child.node.cross(store, layout_type).is_stretch() {
+                            if (item.computed - item.measured).abs() > Scalar::EPSILON {
+                                let target_cross = if child.node.cross(store, layout_type, ctx).is_stretch() {
                                     child.cross
                                 } else {
                                     parent_cross
                                 };
 
                                 if !child.has_layout_constraints
-                                    || !same_f32(child.last_layout_main, item.computed)
-                                    || !same_f32(child.last_layout_cross, target_cross)
+                                    || !same_scalar(child.last_layout_main, item.computed)
+                                    || !same_scalar(child.last_layout_cross, target_cross)
                                 {
                                     let child_size = layout(
                                         child.node,
@@ -1575,6 +3352,7 @@ where
                                         tree,
                                         store,
                                         sublayout,
+                                        ctx,
                                     );
 
                                     child.cross = child_size.cross;
@@ -1588,23 +3366,36 @@ where
                             child.main = item.computed;
                         }
 
+                        ItemType::Before => {
+                            child.main_before = item.computed;
+                        }
+
                         ItemType::After => {
                             child.main_after = item.computed;
                         }
+
+                        ItemType::Between => {
+                            child.main_between = item.computed;
+                        }
                     }
 
-                    main_sum += (child.main + child.main_after) - previous_total;
+                    main_sum += child.main_total() - previous_total;
                 }
             }
         }
     }
 
-    main_sum = children.iter().map(|child| child.main + child.main_after).sum();
-    cross_max = children.iter().map(|child| child.cross).reduce(f32::max).unwrap_or_default();
+    main_sum = children.iter().map(|child| child.main_total()).sum();
+    cross_max = children
+        .iter()
+        .zip(margins.iter())
+        .map(|(child, margins)| child.cross + margins.fixed_cross(parent_cross))
+        .reduce(Scalar::max)
+        .unwrap_or_default();
 
     // Determine auto main and cross size from space and size of children.
     if num_parent_directed_children != 0 {
-        if main.is_auto() || node.min_main(store, parent_layout_type).is_auto() {
+        if main.is_auto() || node.is_content_min_main(store, parent_layout_type, ctx) {
             if parent_layout_type == layout_type {
                 min_main = main_sum + border_main_before + border_main_after + padding_main_before + padding_main_after;
             } else {
@@ -1613,7 +3404,7 @@ where
             }
         }
 
-        if node.max_main(store, parent_layout_type).is_auto() {
+        if node.max_main(store, parent_layout_type, ctx).is_auto() {
             if parent_layout_type == layout_type && main_sum != 0.0 {
                 max_main = main_sum + border_main_before + border_main_after + padding_main_before + padding_main_after;
             } else if cross_max != 0.0 {
@@ -1622,7 +3413,7 @@ where
             }
         }
 
-        if cross.is_auto() || node.min_cross(store, parent_layout_type).is_auto() {
+        if cross.is_auto() || node.is_content_min_cross(store, parent_layout_type, ctx) {
             if parent_layout_type == layout_type {
                 min_cross =
                     cross_max + border_cross_before + border_cross_after + padding_cross_before + padding_cross_after;
@@ -1632,7 +3423,7 @@ where
             }
         }
 
-        if node.max_cross(store, parent_layout_type).is_auto() {
+        if node.max_cross(store, parent_layout_type, ctx).is_auto() {
             if parent_layout_type == layout_type && cross_max != 0.0 {
                 max_cross =
                     cross_max + border_cross_before + border_cross_after + padding_cross_before + padding_cross_after;
@@ -1655,20 +3446,20 @@ where
     parent_main = parent_main - padding_main_before - padding_main_after - border_main_before - border_main_after;
     parent_cross = parent_cross - padding_cross_before - padding_cross_after - border_cross_before - border_cross_after;
 
-    for child in children
-        .iter_mut()
-        .filter(|child| child.node.position_type(store).unwrap_or_default() == PositionType::Relative)
-        .filter(|child| child.node.cross(store, layout_type).is_stretch())
-    {
-        let child_min_cross = if child.node.min_cross(store, layout_type).is_auto() {
+    for (child, margins) in children.iter_mut().zip(margins.iter()) {
+        let Stretch(factor) = child.node.cross(store, layout_type, ctx) else {
+            continue;
+        };
+
+        let child_min_cross = if child.node.is_content_min_cross(store, layout_type, ctx) {
             child.cross
         } else {
-            child.node.min_cross(store, layout_type).to_px(parent_cross, DEFAULT_MIN)
+            child.node.min_cross(store, layout_type, ctx).to_px(parent_cross, DEFAULT_MIN)
         };
 
-        let child_max_cross = child.node.max_cross(store, layout_type).to_px(parent_cross, DEFAULT_MAX);
+        let child_max_cross = child.node.max_cross(store, layout_type, ctx).to_px(parent_cross, DEFAULT_MAX);
 
-        child.cross = parent_cross.clamp(child_min_cross, child_max_cross);
+        child.cross = margins.stretch_cross(parent_cross, factor).min(child_max_cross).max(child_min_cross);
     }
 
     // Re-run relative children with their final resolved constraints so descendant
@@ -1676,23 +3467,23 @@ where
     // Only rerun children that have descendants (skip leaf nodes).
     for child in children
         .iter_mut()
-        .filter(|child| child.node.position_type(store).unwrap_or_default() == PositionType::Relative)
+        .filter(|child| child.node.position_type(store).unwrap_or_default().is_in_flow())
         .filter(|child| child.node.children(tree).next().is_some())
     {
-        let child_main_is_stretch = child.node.main(store, layout_type).is_stretch();
-        let child_cross_is_stretch = child.node.cross(store, layout_type).is_stretch();
+        let child_main_is_stretch = child.node.main(store, layout_type, ctx).is_stretch();
+        let child_cross_is_stretch = child.node.cross(store, layout_type, ctx).is_stretch();
 
         let target_main = if child_main_is_stretch { child.main } else { parent_main };
         let target_cross = if child_cross_is_stretch { child.cross } else { parent_cross };
 
         if child.has_layout_constraints
-            && same_f32(child.last_layout_main, target_main)
-            && same_f32(child.last_layout_cross, target_cross)
+            && same_scalar(child.last_layout_main, target_main)
+            && same_scalar(child.last_layout_cross, target_cross)
         {
             continue;
         }
 
-        let child_size = layout(child.node, layout_type, target_main, target_cross, cache, tree, store, sublayout);
+        let child_size = layout(child.node, layout_type, target_main, target_cross, cache, tree, store, sublayout, ctx);
 
         child.last_layout_main = target_main;
         child.last_layout_cross = target_cross;
@@ -1713,31 +3504,31 @@ where
 
     // Compute space and size of non-flexible absolute children.
     for child in absolute_children.into_iter() {
-        let main = if child.main(store, layout_type).is_stretch() {
-            let child_min_main = child.min_main(store, layout_type).to_px(abs_size_main, DEFAULT_MIN);
-            let child_max_main = child.max_main(store, layout_type).to_px(abs_size_main, DEFAULT_MAX);
+        let main = if child.main(store, layout_type, ctx).is_stretch() {
+            let child_min_main = child.min_main(store, layout_type, ctx).to_px(abs_size_main, DEFAULT_MIN);
+            let child_max_main = child.max_main(store, layout_type, ctx).to_px(abs_size_main, DEFAULT_MAX);
 
-            let child_main_before = child.main_before(store, layout_type).to_px(abs_size_main, 0.0);
-            let child_main_after = child.main_after(store, layout_type).to_px(abs_size_main, 0.0);
+            let child_main_before = child.main_before(store, layout_type, ctx).to_px(abs_size_main, 0.0);
+            let child_main_after = child.main_after(store, layout_type, ctx).to_px(abs_size_main, 0.0);
 
-            abs_size_main.clamp(child_min_main, child_max_main) - child_main_before - child_main_after
+            abs_size_main.min(child_max_main).max(child_min_main) - child_main_before - child_main_after
         } else {
             abs_size_main
         };
 
-        let cross = if child.cross(store, layout_type).is_stretch() {
-            let child_min_cross = child.min_cross(store, layout_type).to_px(abs_size_cross, DEFAULT_MIN);
-            let child_max_cross = child.max_cross(store, layout_type).to_px(abs_size_cross, DEFAULT_MAX);
+        let cross = if child.cross(store, layout_type, ctx).is_stretch() {
+            let child_min_cross = child.min_cross(store, layout_type, ctx).to_px(abs_size_cross, DEFAULT_MIN);
+            let child_max_cross = child.max_cross(store, layout_type, ctx).to_px(abs_size_cross, DEFAULT_MAX);
 
-            let child_cross_before = child.cross_before(store, layout_type).to_px(abs_size_cross, 0.0);
-            let child_cross_after = child.cross_after(store, layout_type).to_px(abs_size_cross, 0.0);
+            let child_cross_before = child.cross_before(store, layout_type, ctx).to_px(abs_size_cross, 0.0);
+            let child_cross_after = child.cross_after(store, layout_type, ctx).to_px(abs_size_cross, 0.0);
 
-            abs_size_cross.clamp(child_min_cross, child_max_cross) - child_cross_before - child_cross_after
+            abs_size_cross.min(child_max_cross).max(child_min_cross) - child_cross_before - child_cross_after
         } else {
             abs_size_cross
         };
 
-        let child_size = layout(child, layout_type, main, cross, cache, tree, store, sublayout);
+        let child_size = layout(child, layout_type, main, cross, cache, tree, store, sublayout, ctx);
 
         let computed_child_main = child_size.main;
         let computed_child_cross = child_size.cross;
@@ -1746,7 +3537,9 @@ where
             node: child,
             cross: computed_child_cross,
             main: computed_child_main,
+            main_before: 0.0,
             main_after: 0.0,
+            main_between: 0.0,
             last_layout_main: 0.0,
             last_layout_cross: 0.0,
             has_layout_constraints: false,
@@ -1763,20 +3556,20 @@ where
 
     // Set size and position of children in the cache.
     let mut main_pos = padding_main_before + border_main_before;
-    for child in children.iter() {
+    for (index, child) in children.iter().enumerate() {
         let child_position = child.node.position_type(store).unwrap_or_default();
 
         match child_position {
             PositionType::Absolute => {
                 let (child_main_before, child_main_after) = if is_row_rtl {
-                    (child.node.main_after(store, layout_type), child.node.main_before(store, layout_type))
+                    (child.node.main_after(store, layout_type, ctx), child.node.main_before(store, layout_type, ctx))
                 } else {
-                    (child.node.main_before(store, layout_type), child.node.main_after(store, layout_type))
+                    (child.node.main_before(store, layout_type, ctx), child.node.main_after(store, layout_type, ctx))
                 };
                 let (child_cross_before, child_cross_after) = if is_rtl && layout_type == LayoutType::Column {
-                    (child.node.cross_after(store, layout_type), child.node.cross_before(store, layout_type))
+                    (child.node.cross_after(store, layout_type, ctx), child.node.cross_before(store, layout_type, ctx))
                 } else {
-                    (child.node.cross_before(store, layout_type), child.node.cross_after(store, layout_type))
+                    (child.node.cross_before(store, layout_type, ctx), child.node.cross_after(store, layout_type, ctx))
                 };
 
                 let parent_main = parent_main + padding_main_before + padding_main_after;
@@ -1798,7 +3591,7 @@ where
                 );
             }
 
-            PositionType::Relative => {
+            PositionType::Relative | PositionType::Sticky => {
                 let (mut child_main_pos, mut child_cross_pos) = match alignment {
                     Alignment::TopLeft => (0.0, 0.0),
                     Alignment::TopCenter => (0.0, 0.5),
@@ -1816,29 +3609,44 @@ where
                 }
 
                 child_main_pos *= parent_main - main_sum;
-                child_cross_pos *= parent_cross - child.cross;
-
-                if let Some(main_scroll) = node.main_scroll(store, layout_type) {
-                    child_main_pos = main_scroll
-                }
-
-                if let Some(cross_scroll) = node.cross_scroll(store, layout_type) {
-                    child_cross_pos = cross_scroll
-                }
+                child_cross_pos = margins[index].cross_position(parent_cross, child.cross, child_cross_pos);
 
                 cache.set_rect(
                     child.node,
                     layout_type,
-                    main_pos + child_main_pos,
+                    main_pos + child_main_pos + child.main_before,
                     child_cross_pos + padding_cross_before + border_cross_before,
                     child.main,
                     child.cross,
                 );
-                main_pos += child.main + child.main_after;
+                main_pos += child.main_total();
             }
+
+            // Fixed children are laid out against the root by `layout_out_of_flow`.
+            PositionType::Fixed => {}
         };
     }
 
+    // Content and padding boxes in main/cross axes.
+    let content = Bounds {
+        x: padding_main_before + border_main_before,
+        y: padding_cross_before + border_cross_before,
+        width: parent_main,
+        height: parent_cross,
+    };
+    let padding = Bounds {
+        x: border_main_before,
+        y: border_cross_before,
+        width: parent_main + padding_main_before + padding_main_after,
+        height: parent_cross + padding_cross_before + padding_cross_after,
+    };
+
+    if layout_type == LayoutType::Column {
+        scroll_content(node, content.transpose(), padding.transpose(), cache, tree, store);
+    } else {
+        scroll_content(node, content, padding, cache, tree, store);
+    }
+
     // Return the computed size, propagating it back up the tree.
     Size { main: computed_main, cross: computed_cross }
 }
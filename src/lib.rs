@@ -13,4 +13,7 @@ pub mod node;
 pub use node::*;
 
 mod layout;
-use layout::layout;
+use layout::{
+    layout, layout_anchored, layout_clip, layout_out_of_flow, layout_sticky, snap_to_pixels, stacking_order,
+    window_bounds,
+};
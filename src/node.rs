@@ -1,4 +1,10 @@
-use crate::{layout, types::*, Cache, LayoutWrap};
+use crate::{
+    layout, layout_anchored, layout_clip, layout_out_of_flow, layout_sticky, snap_to_pixels, stacking_order, types::*,
+    window_bounds, Cache, LayoutCache, LayoutWrap,
+};
+
+/// The font size used to resolve font-relative units when the root node does not specify one.
+const DEFAULT_FONT_SIZE: Scalar = 16.0;
 
 /// A `Node` represents a layout element which can be sized and positioned based on
 /// a number of layout properties.
@@ -21,7 +27,10 @@ pub trait Node: Sized {
     where
         Self: 't;
     /// A type representing a key to store and retrieve values from the [`Cache`].
-    type CacheKey: std::fmt::Debug;
+    ///
+    /// Keys are compared for equality to find the node given as the [`anchor`](crate::Node::anchor) of another node,
+    /// and are hashed to reuse the content sizes measured for each node during [`layout`](crate::Node::layout).
+    type CacheKey: std::fmt::Debug + Eq + std::hash::Hash;
     /// A type representing a context which can be used to save/load state when computing [content size](crate::Node::content_size).
     /// For example, a `TextContext` which could be used to measure (and cache) the size of text, which could
     /// then be used to size an `Auto` layout node using content size.
@@ -32,6 +41,11 @@ pub trait Node: Sized {
     /// The algorithm recurses down the tree, in depth-first order, and performs
     /// layout on every node starting from the input `node`.
     ///
+    /// If the cache stores [layout records](crate::Cache::layout_record), nodes which are given the same constraints
+    /// as in their last layout are not measured and arranged again. If it also stores
+    /// [post-layout records](crate::Cache::post_layout_record), the passes which run after layout, such as sticky
+    /// positioning, anchoring, clipping and the paint order, skip the descendants of those nodes as well.
+    ///
     /// # Arguments
     ///
     /// * `cache` - A mutable reference to the [`Cache`].
@@ -46,8 +60,12 @@ pub trait Node: Sized {
         store: &Self::Store,
         sublayout: &mut Self::SubLayout<'_>,
     ) -> Size {
-        let width = self.width(store).unwrap_or(Units::Pixels(0.0)).to_px(0.0, 0.0);
-        let height = self.height(store).unwrap_or(Units::Pixels(0.0)).to_px(0.0, 0.0);
+        // Content sizes measured during this layout are reused by every pass.
+        let cache = &mut LayoutCache::new(cache);
+
+        let numeric_mode = self.numeric_mode(store).unwrap_or_default();
+        let width = numeric_mode.quantize(self.width(store).unwrap_or(Units::Pixels(0.0)).to_px(0.0, 0.0));
+        let height = numeric_mode.quantize(self.height(store).unwrap_or(Units::Pixels(0.0)).to_px(0.0, 0.0));
 
         cache.set_bounds(self, cache.posx(self), cache.posy(self), width, height);
 
@@ -58,7 +76,48 @@ pub trait Node: Sized {
             LayoutType::Column => (height, width), // Column: main=height, cross=width
         };
 
-        layout(self, layout_type, parent_main, parent_cross, cache, tree, store, sublayout)
+        // Viewport-relative units resolve against the size of the root node.
+        let font_size = self.font_size(store).unwrap_or(DEFAULT_FONT_SIZE);
+        let ctx = UnitContext {
+            viewport_width: width,
+            viewport_height: height,
+            font_size,
+            root_font_size: font_size,
+            width: None,
+            height: None,
+            numeric_mode,
+        };
+
+        let size = layout(self, layout_type, parent_main, parent_cross, cache, tree, store, sublayout, &ctx);
+
+        layout_out_of_flow(self, 0.0, 0.0, width, height, None, cache, tree, store, sublayout, &ctx);
+
+        let (posx, posy) = (cache.posx(self), cache.posy(self));
+        layout_sticky(self, (posx, posx + width), (posy, posy + height), (0.0, 0.0), cache, tree, store, &ctx);
+
+        // Anchored nodes are placed against the positions of their anchors after sticky positioning.
+        layout_anchored(self, width, height, cache, tree, store);
+
+        // Edges are rounded to the device pixel grid if snapping is enabled, and then to the fixed-point grid, so that
+        // every output is a multiple of the step of the numeric mode. Character cells are the device grid in cell mode,
+        // so edges are only rounded to whole cells.
+        let snapping = self.pixel_snapping(store).unwrap_or_default();
+        if snapping == PixelSnapping::Edges && numeric_mode != NumericMode::Cells {
+            let scale_factor = self.scale_factor(store).filter(|scale| *scale > 0.0).unwrap_or(1.0);
+            snap_to_pixels(self, (0.0, 0.0), (0.0, 0.0), scale_factor, cache, tree, store);
+        }
+
+        if let Some(step) = numeric_mode.step() {
+            snap_to_pixels(self, (0.0, 0.0), (0.0, 0.0), 1.0 / step, cache, tree, store);
+        }
+
+        layout_clip(self, None, None, cache, tree, store, &ctx);
+
+        stacking_order(self, cache, tree, store);
+
+        window_bounds(self, Transform::IDENTITY, cache, tree, store);
+
+        size
     }
 
     /// Returns a key which can be used to set/get computed layout data from the [`cache`](crate::Cache).
@@ -88,6 +147,14 @@ pub trait Node: Sized {
         None
     }
 
+    /// Returns the font size of the node, used to resolve font-relative [`Units`].
+    ///
+    /// Defaults to `None`, in which case the font size is inherited from the parent.
+    /// If the root node does not provide a font size then a default of 16 pixels is used.
+    fn font_size(&self, _store: &Self::Store) -> Option<Scalar> {
+        None
+    }
+
     /// Returns the alignment of the node.
     fn alignment(&self, store: &Self::Store) -> Option<Alignment>;
 
@@ -109,15 +176,71 @@ pub trait Node: Sized {
     /// Returns the desired bottom-side space of the node.
     fn bottom(&self, store: &Self::Store) -> Option<Units>;
 
+    /// Returns the minimum left-side space of the node.
+    ///
+    /// Only applies to relative children of a row, column, or wrapping container. Defaults to `None`.
+    fn min_left(&self, _store: &Self::Store) -> Option<Units> {
+        None
+    }
+
+    /// Returns the maximum left-side space of the node.
+    ///
+    /// Only applies to relative children of a row, column, or wrapping container. Defaults to `None`.
+    fn max_left(&self, _store: &Self::Store) -> Option<Units> {
+        None
+    }
+
+    /// Returns the minimum right-side space of the node.
+    ///
+    /// Only applies to relative children of a row, column, or wrapping container. Defaults to `None`.
+    fn min_right(&self, _store: &Self::Store) -> Option<Units> {
+        None
+    }
+
+    /// Returns the maximum right-side space of the node.
+    ///
+    /// Only applies to relative children of a row, column, or wrapping container. Defaults to `None`.
+    fn max_right(&self, _store: &Self::Store) -> Option<Units> {
+        None
+    }
+
+    /// Returns the minimum top-side space of the node.
+    ///
+    /// Only applies to relative children of a row, column, or wrapping container. Defaults to `None`.
+    fn min_top(&self, _store: &Self::Store) -> Option<Units> {
+        None
+    }
+
+    /// Returns the maximum top-side space of the node.
+    ///
+    /// Only applies to relative children of a row, column, or wrapping container. Defaults to `None`.
+    fn max_top(&self, _store: &Self::Store) -> Option<Units> {
+        None
+    }
+
+    /// Returns the minimum bottom-side space of the node.
+    ///
+    /// Only applies to relative children of a row, column, or wrapping container. Defaults to `None`.
+    fn min_bottom(&self, _store: &Self::Store) -> Option<Units> {
+        None
+    }
+
+    /// Returns the maximum bottom-side space of the node.
+    ///
+    /// Only applies to relative children of a row, column, or wrapping container. Defaults to `None`.
+    fn max_bottom(&self, _store: &Self::Store) -> Option<Units> {
+        None
+    }
+
     /// Returns the width and height of the node if its desired width and/or desired height are auto and the node has no children.
     /// This can be used to size the node based on visual content (such as text), or to apply an aspect ratio size constraint.
     fn content_size(
         &self,
         store: &Self::Store,
         sublayout: &mut Self::SubLayout<'_>,
-        parent_width: Option<f32>,
-        parent_height: Option<f32>,
-    ) -> Option<(f32, f32)>;
+        parent_width: Option<Scalar>,
+        parent_height: Option<Scalar>,
+    ) -> Option<(Scalar, Scalar)>;
 
     /// Returns the desired left-side child-space of the node.
     fn padding_left(&self, store: &Self::Store) -> Option<Units>;
@@ -174,10 +297,114 @@ pub trait Node: Sized {
     fn border_bottom(&self, store: &Self::Store) -> Option<Units>;
 
     /// Returns the vertical scroll offset of the node.
-    fn vertical_scroll(&self, store: &Self::Store) -> Option<f32>;
+    ///
+    /// The offset translates the relative children of the node from their aligned positions,
+    /// where a negative offset moves the content up.
+    fn vertical_scroll(&self, store: &Self::Store) -> Option<Scalar>;
 
     /// Returns the horizontal scroll offset of the node.
-    fn horizontal_scroll(&self, store: &Self::Store) -> Option<f32>;
+    ///
+    /// The offset translates the relative children of the node from their aligned positions,
+    /// where a negative offset moves the content to the left.
+    fn horizontal_scroll(&self, store: &Self::Store) -> Option<Scalar>;
+
+    /// Returns the overflow mode of the node on the horizontal axis.
+    ///
+    /// Defaults to `None` which is treated as [`Overflow::Visible`].
+    fn horizontal_overflow(&self, _store: &Self::Store) -> Option<Overflow> {
+        None
+    }
+
+    /// Returns the overflow mode of the node on the vertical axis.
+    ///
+    /// Defaults to `None` which is treated as [`Overflow::Visible`].
+    fn vertical_overflow(&self, _store: &Self::Store) -> Option<Overflow> {
+        None
+    }
+
+    /// Returns whether the scroll offsets of the node are clamped to the scrollable range of its content.
+    ///
+    /// Defaults to `None` which is treated as `false`.
+    fn clamp_scroll(&self, _store: &Self::Store) -> Option<bool> {
+        None
+    }
+
+    /// Returns whether the node is a containing block for its absolute descendants.
+    ///
+    /// An absolute node is positioned against the padding box of its nearest ancestor which is a containing block,
+    /// or against its parent if there is no such ancestor.
+    ///
+    /// Defaults to `None` which is treated as `false`.
+    fn containing_block(&self, _store: &Self::Store) -> Option<bool> {
+        None
+    }
+
+    /// Returns the key of the node that an absolute node is placed next to, instead of being positioned within its
+    /// parent.
+    ///
+    /// Defaults to `None`, in which case the node is positioned by its insets as usual.
+    fn anchor(&self, _store: &Self::Store) -> Option<Self::CacheKey> {
+        None
+    }
+
+    /// Returns the placement of an anchored node relative to its anchor.
+    ///
+    /// Defaults to `None` which is treated as [`Placement::BelowStart`].
+    fn placement(&self, _store: &Self::Store) -> Option<Placement> {
+        None
+    }
+
+    /// Returns the z-index of the node, which determines the order in which it is painted.
+    ///
+    /// A node with a z-index forms a stacking context. It is painted together with its descendants, before the other
+    /// content of its parent stacking context if the z-index is negative, and after it otherwise.
+    ///
+    /// Defaults to `None`, in which case the node is painted in tree order within its stacking context.
+    fn z_index(&self, _store: &Self::Store) -> Option<i32> {
+        None
+    }
+
+    /// Returns the transform of the node, which is applied about its center after layout.
+    ///
+    /// The transform does not affect the layout of the node or its siblings, but is applied to the node and its
+    /// descendants when computing their clip rectangles, window-space bounds, and hit testing.
+    ///
+    /// Defaults to `None`, which is treated as [`Transform::IDENTITY`].
+    fn transform(&self, _store: &Self::Store) -> Option<Transform> {
+        None
+    }
+
+    /// Returns whether the node can receive focus when navigating with [`navigate`](crate::navigate).
+    ///
+    /// Defaults to `None` which is treated as `false`.
+    fn focusable(&self, _store: &Self::Store) -> Option<bool> {
+        None
+    }
+
+    /// Returns the number of device pixels per layout pixel, used to [snap](crate::Node::pixel_snapping) bounds to
+    /// the device pixel grid.
+    ///
+    /// Only the value of the root node passed to [`layout`](crate::Node::layout) is used.
+    /// Defaults to `None` which is treated as `1.0`.
+    fn scale_factor(&self, _store: &Self::Store) -> Option<Scalar> {
+        None
+    }
+
+    /// Returns whether the computed bounds of nodes are rounded to the device pixel grid after layout.
+    ///
+    /// Only the value of the root node passed to [`layout`](crate::Node::layout) is used.
+    /// Defaults to `None` which is treated as [`PixelSnapping::None`].
+    fn pixel_snapping(&self, _store: &Self::Store) -> Option<PixelSnapping> {
+        None
+    }
+
+    /// Returns the numeric mode of the layout, which determines how computed values are rounded.
+    ///
+    /// Only the value of the root node passed to [`layout`](crate::Node::layout) is used.
+    /// Defaults to `None` which is treated as [`NumericMode::Float`].
+    fn numeric_mode(&self, _store: &Self::Store) -> Option<NumericMode> {
+        None
+    }
 
     fn grid_columns(&self, store: &Self::Store) -> Option<Vec<Units>>;
 
@@ -194,97 +421,212 @@ pub trait Node: Sized {
 
 /// Helper trait used internally for converting layout properties into a direction-agnostic value.
 pub(crate) trait NodeExt: Node {
-    fn main(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        match parent_layout_type {
+    // Returns the unit context of the node, inheriting the font size from the given parent context if not specified.
+    // The size of the node is not yet known, so sizes from the parent context are cleared.
+    //
+    // Properties read by the parent of the node (size, constraints and spacing) are resolved with this context,
+    // while properties read by the node itself (padding, border and gap) are resolved with the context given by
+    // the caller, which holds the computed size of the node.
+    fn unit_context(&self, store: &Self::Store, ctx: &UnitContext) -> UnitContext {
+        UnitContext { font_size: self.font_size(store).unwrap_or(ctx.font_size), width: None, height: None, ..*ctx }
+    }
+
+    fn main(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        let units = match parent_layout_type {
             LayoutType::Row | LayoutType::Overlay | LayoutType::Grid => {
                 self.width(store).unwrap_or(Units::Stretch(1.0))
             }
             LayoutType::Column => self.height(store).unwrap_or(Units::Stretch(1.0)),
+        };
+
+        let units = units.resolve(&self.unit_context(store, ctx));
+
+        // An out-of-flow node with an auto size and both insets set fills the space between them.
+        if units.is_auto()
+            && self.is_out_of_flow(store)
+            && is_inset(self.main_before(store, parent_layout_type, ctx))
+            && is_inset(self.main_after(store, parent_layout_type, ctx))
+        {
+            Units::Stretch(1.0)
+        } else {
+            units
         }
     }
 
-    fn min_main(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap_default(
-            store,
-            |store| self.min_width(store),
-            |store| self.min_height(store),
-            Units::Pixels(0.0),
-        )
+    fn min_main(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(
+                store,
+                |store| self.min_width(store),
+                |store| self.min_height(store),
+                Units::Pixels(0.0),
+            )
+            .resolve(&self.unit_context(store, ctx))
     }
 
-    fn max_main(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap_default(
-            store,
-            |store| self.max_width(store),
-            |store| self.max_height(store),
-            Units::Pixels(f32::MAX),
-        )
+    fn max_main(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(
+                store,
+                |store| self.max_width(store),
+                |store| self.max_height(store),
+                Units::Pixels(Scalar::MAX),
+            )
+            .resolve(&self.unit_context(store, ctx))
     }
 
-    fn cross(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        match parent_layout_type {
+    fn cross(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        let units = match parent_layout_type {
             LayoutType::Row | LayoutType::Overlay | LayoutType::Grid => {
                 self.height(store).unwrap_or(Units::Stretch(1.0))
             }
             LayoutType::Column => self.width(store).unwrap_or(Units::Stretch(1.0)),
+        };
+
+        let units = units.resolve(&self.unit_context(store, ctx));
+
+        if units.is_auto()
+            && self.is_out_of_flow(store)
+            && is_inset(self.cross_before(store, parent_layout_type, ctx))
+            && is_inset(self.cross_after(store, parent_layout_type, ctx))
+        {
+            Units::Stretch(1.0)
+        } else {
+            units
         }
     }
 
-    fn min_cross(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap_default(
-            store,
-            |store| self.min_height(store),
-            |store| self.min_width(store),
-            Units::Pixels(0.0),
-        )
+    fn min_cross(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(
+                store,
+                |store| self.min_height(store),
+                |store| self.min_width(store),
+                Units::Pixels(0.0),
+            )
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    fn max_cross(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(
+                store,
+                |store| self.max_height(store),
+                |store| self.max_width(store),
+                Units::Pixels(Scalar::MAX),
+            )
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    // Returns true if the node is positioned out-of-line, independently of its siblings.
+    fn is_out_of_flow(&self, store: &Self::Store) -> bool {
+        !self.position_type(store).unwrap_or_default().is_in_flow()
     }
 
-    fn max_cross(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap_default(
-            store,
-            |store| self.max_height(store),
-            |store| self.max_width(store),
-            Units::Pixels(f32::MAX),
-        )
+    // Returns true if the minimum main size of the node is determined by its content. This is the case for an auto
+    // minimum size, unless the node clips its content on that axis.
+    fn is_content_min_main(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> bool {
+        self.min_main(store, parent_layout_type, ctx).is_auto()
+            && !parent_layout_type
+                .select_unwrap(store, |store| self.horizontal_overflow(store), |store| self.vertical_overflow(store))
+                .is_clipped()
     }
 
-    fn main_before(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap_default(store, |store| self.left(store), |store| self.top(store), Units::Auto)
+    // Returns true if the minimum cross size of the node is determined by its content.
+    fn is_content_min_cross(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> bool {
+        self.min_cross(store, parent_layout_type, ctx).is_auto()
+            && !parent_layout_type
+                .select_unwrap(store, |store| self.vertical_overflow(store), |store| self.horizontal_overflow(store))
+                .is_clipped()
     }
 
-    fn main_after(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap_default(
-            store,
-            |store| self.right(store),
-            |store| self.bottom(store),
-            Units::Auto,
-        )
+    fn main_before(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.left(store), |store| self.top(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
     }
 
-    fn cross_before(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap_default(store, |store| self.top(store), |store| self.left(store), Units::Auto)
+    fn main_after(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.right(store), |store| self.bottom(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
     }
 
-    fn cross_after(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap_default(
-            store,
-            |store| self.bottom(store),
-            |store| self.right(store),
-            Units::Auto,
-        )
+    fn cross_before(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.top(store), |store| self.left(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
     }
 
-    fn padding_main_before(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        if parent_layout_type == LayoutType::Row && self.direction(store).unwrap_or_default() == Direction::RightToLeft
+    fn cross_after(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.bottom(store), |store| self.right(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    fn min_main_before(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.min_left(store), |store| self.min_top(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    fn max_main_before(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.max_left(store), |store| self.max_top(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    fn min_main_after(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.min_right(store), |store| self.min_bottom(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    fn max_main_after(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.max_right(store), |store| self.max_bottom(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    fn min_cross_before(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.min_top(store), |store| self.min_left(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    fn max_cross_before(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.max_top(store), |store| self.max_left(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    fn min_cross_after(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.min_bottom(store), |store| self.min_right(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    fn max_cross_after(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap_default(store, |store| self.max_bottom(store), |store| self.max_right(store), Units::Auto)
+            .resolve(&self.unit_context(store, ctx))
+    }
+
+    fn padding_main_before(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        let units = if parent_layout_type == LayoutType::Row
+            && self.direction(store).unwrap_or_default() == Direction::RightToLeft
         {
             self.padding_right(store).unwrap_or_default()
         } else {
             parent_layout_type.select_unwrap(store, |store| self.padding_left(store), |store| self.padding_top(store))
-        }
+        };
+
+        units.resolve(ctx)
     }
 
-    fn padding_main_after(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        if parent_layout_type == LayoutType::Row && self.direction(store).unwrap_or_default() == Direction::RightToLeft
+    fn padding_main_after(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        let units = if parent_layout_type == LayoutType::Row
+            && self.direction(store).unwrap_or_default() == Direction::RightToLeft
         {
             self.padding_left(store).unwrap_or_default()
         } else {
@@ -293,84 +635,110 @@ pub(crate) trait NodeExt: Node {
                 |store| self.padding_right(store),
                 |store| self.padding_bottom(store),
             )
-        }
+        };
+
+        units.resolve(ctx)
     }
 
-    fn padding_cross_before(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap(store, |store| self.padding_top(store), |store| self.padding_left(store))
+    fn padding_cross_before(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap(store, |store| self.padding_top(store), |store| self.padding_left(store))
+            .resolve(ctx)
     }
 
-    fn padding_cross_after(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap(store, |store| self.padding_bottom(store), |store| self.padding_right(store))
+    fn padding_cross_after(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap(store, |store| self.padding_bottom(store), |store| self.padding_right(store))
+            .resolve(ctx)
     }
 
-    fn main_between(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap(store, |store| self.horizontal_gap(store), |store| self.vertical_gap(store))
+    fn main_between(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap(store, |store| self.horizontal_gap(store), |store| self.vertical_gap(store))
+            .resolve(ctx)
     }
 
-    fn min_main_between(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap(
-            store,
-            |store| self.min_horizontal_gap(store),
-            |store| self.min_vertical_gap(store),
-        )
+    fn min_main_between(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap(store, |store| self.min_horizontal_gap(store), |store| self.min_vertical_gap(store))
+            .resolve(ctx)
     }
 
-    fn max_main_between(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap(
-            store,
-            |store| self.max_horizontal_gap(store),
-            |store| self.max_vertical_gap(store),
-        )
+    fn max_main_between(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap(store, |store| self.max_horizontal_gap(store), |store| self.max_vertical_gap(store))
+            .resolve(ctx)
     }
 
-    fn cross_between(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap(store, |store| self.vertical_gap(store), |store| self.horizontal_gap(store))
+    fn cross_between(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap(store, |store| self.vertical_gap(store), |store| self.horizontal_gap(store))
+            .resolve(ctx)
     }
 
-    fn border_main_before(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap(store, |store| self.border_left(store), |store| self.border_top(store))
+    fn border_main_before(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap(store, |store| self.border_left(store), |store| self.border_top(store))
+            .resolve(ctx)
     }
 
-    fn border_main_after(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap(store, |store| self.border_right(store), |store| self.border_bottom(store))
+    fn border_main_after(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap(store, |store| self.border_right(store), |store| self.border_bottom(store))
+            .resolve(ctx)
     }
 
-    fn border_cross_before(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap(store, |store| self.border_top(store), |store| self.border_left(store))
+    fn border_cross_before(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap(store, |store| self.border_top(store), |store| self.border_left(store))
+            .resolve(ctx)
     }
 
-    fn border_cross_after(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Units {
-        parent_layout_type.select_unwrap(store, |store| self.border_bottom(store), |store| self.border_right(store))
+    fn border_cross_after(&self, store: &Self::Store, parent_layout_type: LayoutType, ctx: &UnitContext) -> Units {
+        parent_layout_type
+            .select_unwrap(store, |store| self.border_bottom(store), |store| self.border_right(store))
+            .resolve(ctx)
     }
 
-    fn content_sizing(
+    #[allow(clippy::too_many_arguments)]
+    fn content_sizing<C: Cache<Node = Self>>(
         &self,
+        cache: &mut C,
         store: &Self::Store,
         sublayout: &mut Self::SubLayout<'_>,
         parent_layout_type: LayoutType,
-        parent_main: Option<f32>,
-        parent_cross: Option<f32>,
-    ) -> Option<(f32, f32)> {
-        match parent_layout_type {
-            LayoutType::Row | LayoutType::Overlay | LayoutType::Grid => {
-                self.content_size(store, sublayout, parent_main, parent_cross)
-            }
+        parent_main: Option<Scalar>,
+        parent_cross: Option<Scalar>,
+        ctx: &UnitContext,
+    ) -> Option<(Scalar, Scalar)> {
+        let (width, height) = match parent_layout_type {
+            LayoutType::Row | LayoutType::Overlay | LayoutType::Grid => (parent_main, parent_cross),
+            LayoutType::Column => (parent_cross, parent_main),
+        };
 
-            LayoutType::Column => {
-                self.content_size(store, sublayout, parent_cross, parent_main).map(|(width, height)| (height, width))
+        // Content is measured at most once for each pair of constraints.
+        let size = match cache.content_measurement(self, width, height) {
+            Some(size) => size,
+            None => {
+                let size = self.content_size(store, sublayout, width, height)?;
+                cache.set_content_measurement(self, width, height, size);
+                size
             }
-        }
-    }
+        };
 
-    fn cross_scroll(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Option<f32> {
-        parent_layout_type.select(store, |store| self.vertical_scroll(store), |store| self.horizontal_scroll(store))
-    }
+        let (main, cross) = match parent_layout_type {
+            LayoutType::Row | LayoutType::Overlay | LayoutType::Grid => size,
+            LayoutType::Column => (size.1, size.0),
+        };
 
-    fn main_scroll(&self, store: &Self::Store, parent_layout_type: LayoutType) -> Option<f32> {
-        parent_layout_type.select(store, |store| self.horizontal_scroll(store), |store| self.vertical_scroll(store))
+        Some((ctx.numeric_mode.round_content(main), ctx.numeric_mode.round_content(cross)))
     }
 }
 
 // Implement `NodeExt` for all types which implement `Node`.
 impl<N: Node> NodeExt for N {}
+
+// Returns true if an inset of an out-of-flow node is a fixed distance from the edge of its containing block.
+fn is_inset(units: Units) -> bool {
+    units.is_pixels() || units.is_percentage()
+}
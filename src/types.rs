@@ -1,3 +1,20 @@
+/// The scalar type used for all layout values.
+///
+/// This is `f32` by default, or `f64` when the `f64` feature is enabled, for layouts which are large enough that `f32`
+/// precision errors become visible.
+///
+/// The `f64` feature is not additive: if any crate in a dependency graph enables it, `Scalar` is `f64` for every crate
+/// which depends on morphorm. Code which uses morphorm should therefore use `Scalar` rather than `f32`.
+#[cfg(not(feature = "f64"))]
+pub type Scalar = f32;
+
+/// The scalar type used for all layout values.
+///
+/// This is `f64` because the `f64` feature is enabled, which applies to every crate in the dependency graph which
+/// depends on morphorm.
+#[cfg(feature = "f64")]
+pub type Scalar = f64;
+
 /// The layout type determines how the nodes will position its parent-directed children.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LayoutType {
@@ -13,19 +30,6 @@ pub enum LayoutType {
 }
 
 impl LayoutType {
-    // Helper function for selecting between optional values depending on the layout type.
-    pub(crate) fn select<T: Default, S>(
-        &self,
-        s: S,
-        first: impl FnOnce(S) -> Option<T>,
-        second: impl FnOnce(S) -> Option<T>,
-    ) -> Option<T> {
-        match self {
-            LayoutType::Row | LayoutType::Overlay => first(s),
-            LayoutType::Column | LayoutType::Grid => second(s),
-        }
-    }
-
     // Helper function for selecting between optional values depending on the layout type.
     pub(crate) fn select_unwrap<T: Default, S>(
         &self,
@@ -73,6 +77,18 @@ pub enum PositionType {
     /// Node is positioned relative to parent and in-line with siblings.
     #[default]
     Relative,
+    /// Node is positioned in-line with siblings, and then kept within the visible area of its nearest scroll container
+    /// by its left, right, top and bottom insets once scroll offsets are applied.
+    Sticky,
+    /// Node is positioned out-of-line relative to the root node, ignoring the scroll offsets of its ancestors.
+    Fixed,
+}
+
+impl PositionType {
+    /// Returns true if the node is laid out in-line with its siblings.
+    pub fn is_in_flow(&self) -> bool {
+        matches!(self, PositionType::Relative | PositionType::Sticky)
+    }
 }
 
 impl std::fmt::Display for PositionType {
@@ -80,6 +96,8 @@ impl std::fmt::Display for PositionType {
         match self {
             PositionType::Absolute => write!(f, "absolute"),
             PositionType::Relative => write!(f, "relative"),
+            PositionType::Sticky => write!(f, "sticky"),
+            PositionType::Fixed => write!(f, "fixed"),
         }
     }
 }
@@ -107,12 +125,12 @@ impl std::fmt::Display for Direction {
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub enum Units {
     /// A number of logical pixels.
-    Pixels(f32),
+    Pixels(Scalar),
     /// A percentage of the parent dimension.
     ///
     /// A percentage of the parent's width when applied to left, width, right properties.
     /// A percentage of the parent's height when applied to top, height, bottom properties.
-    Percentage(f32),
+    Percentage(Scalar),
     /// A factor of the remaining free space.
     ///
     /// The remaining free space is the parent space minus the space and size of any fixed-size nodes in that axis.
@@ -120,7 +138,56 @@ pub enum Units {
     ///
     /// For example, given two stretch nodes with factors of 1.0 and 2.0 respectively. The first will occupy 1/3 of the
     /// remaining free space while the second will occupy 2/3 of the remaining free space.
-    Stretch(f32),
+    Stretch(Scalar),
+    /// A percentage of the viewport width.
+    ///
+    /// The viewport is the size of the root node passed to [`layout`](crate::Node::layout), regardless of the size of
+    /// any intermediate ancestors.
+    ViewportWidth(Scalar),
+    /// A percentage of the viewport height.
+    ViewportHeight(Scalar),
+    /// A percentage of the smaller of the viewport width and height.
+    ViewportMin(Scalar),
+    /// A percentage of the larger of the viewport width and height.
+    ViewportMax(Scalar),
+    /// A multiple of the font size of the node.
+    ///
+    /// The font size is provided by the [`font_size`](crate::Node::font_size) property of the node, or inherited
+    /// from the nearest ancestor which provides one.
+    Em(Scalar),
+    /// A multiple of the font size of the root node.
+    Rem(Scalar),
+    /// A percentage of the computed width of the node itself.
+    ///
+    /// Applies to the size, padding, border and gap properties of the node. When used for the height of a node, the
+    /// width is resolved first, allowing for an aspect ratio. When used for the width, the value resolves to zero.
+    SelfWidth(Scalar),
+    /// A percentage of the computed height of the node itself.
+    ///
+    /// Applies to the size, padding, border and gap properties of the node. When used for the width of a node, the
+    /// height is resolved first, allowing for an aspect ratio. When used for the height, the value resolves to zero.
+    SelfHeight(Scalar),
+    /// A percentage of the smaller of the computed width and height of the node itself.
+    ///
+    /// When used for the size of a node, only the other axis is considered.
+    SelfMin(Scalar),
+    /// A percentage of the larger of the computed width and height of the node itself.
+    ///
+    /// When used for the size of a node, only the other axis is considered.
+    SelfMax(Scalar),
+    /// The smallest size the node can take without overflowing its content.
+    ///
+    /// For a node without children this is the [`content_size`](crate::Node::content_size) of the node when
+    /// constrained to zero on the same axis, e.g. the narrowest wrap of a text label. For a node with children
+    /// this is computed from the min-content sizes of its subtree.
+    MinContent,
+    /// The size the node would take given unlimited space on the same axis.
+    ///
+    /// For a node without children this is the unconstrained [`content_size`](crate::Node::content_size) of the node.
+    /// For a node with children this is computed from the max-content sizes of its subtree.
+    MaxContent,
+    /// The available space of the parent, clamped between the min-content and max-content sizes of the node.
+    FitContent,
     /// Automatically determine the value.
     ///
     /// When applied to space (left, right, top, bottom) the spacing may be overridden by the parent's child-space on the same side.
@@ -134,24 +201,61 @@ pub enum Units {
 
 impl Units {
     /// Returns the units converted to pixels or a provided default.
-    pub fn to_px(&self, parent_value: f32, default: f32) -> f32 {
+    ///
+    /// Viewport-relative and font-relative units must first be converted with [`resolve`](Units::resolve),
+    /// otherwise the default is returned. Intrinsic sizing keywords are resolved during layout and also return the default.
+    pub fn to_px(&self, parent_value: Scalar, default: Scalar) -> Scalar {
         match self {
             Units::Pixels(pixels) => *pixels,
             Units::Percentage(percentage) => (percentage / 100.0) * parent_value,
-            Units::Stretch(_) => default,
-            Units::Auto => default,
+            _ => default,
         }
     }
 
-    pub fn to_px_clamped(&self, parent_value: f32, default: f32, min: Units, max: Units) -> f32 {
-        let min = min.to_px(parent_value, f32::MIN);
-        let max = max.to_px(parent_value, f32::MAX);
+    pub fn to_px_clamped(&self, parent_value: Scalar, default: Scalar, min: Units, max: Units) -> Scalar {
+        let min = min.to_px(parent_value, Scalar::MIN);
+        let max = max.to_px(parent_value, Scalar::MAX);
 
         match self {
             Units::Pixels(pixels) => pixels.min(max).max(min),
             Units::Percentage(percentage) => ((percentage / 100.0) * parent_value).min(max).max(min),
-            Units::Stretch(_) => default.min(max).max(min),
-            Units::Auto => default.min(max).max(min),
+            _ => default.min(max).max(min),
+        }
+    }
+
+    /// Returns the units with any viewport-relative, font-relative or self-relative value converted to pixels using
+    /// the given context.
+    ///
+    /// Units which are resolved relative to the parent, such as percentages, are returned unchanged, as are
+    /// self-relative units when the referenced size of the node is not known.
+    ///
+    /// Values in pixels are rounded according to the [`NumericMode`] of the context.
+    pub fn resolve(&self, context: &UnitContext) -> Self {
+        let units = match *self {
+            Units::ViewportWidth(val) => Units::Pixels(val / 100.0 * context.viewport_width),
+            Units::ViewportHeight(val) => Units::Pixels(val / 100.0 * context.viewport_height),
+            Units::ViewportMin(val) => Units::Pixels(val / 100.0 * context.viewport_width.min(context.viewport_height)),
+            Units::ViewportMax(val) => Units::Pixels(val / 100.0 * context.viewport_width.max(context.viewport_height)),
+            Units::Em(val) => Units::Pixels(val * context.font_size),
+            Units::Rem(val) => Units::Pixels(val * context.root_font_size),
+            Units::SelfWidth(val) => context.width.map_or(*self, |width| Units::Pixels(val / 100.0 * width)),
+            Units::SelfHeight(val) => context.height.map_or(*self, |height| Units::Pixels(val / 100.0 * height)),
+            Units::SelfMin(val) => match (context.width, context.height) {
+                (Some(width), Some(height)) => Units::Pixels(val / 100.0 * width.min(height)),
+                (Some(size), None) | (None, Some(size)) => Units::Pixels(val / 100.0 * size),
+                (None, None) => *self,
+            },
+            Units::SelfMax(val) => match (context.width, context.height) {
+                (Some(width), Some(height)) => Units::Pixels(val / 100.0 * width.max(height)),
+                (Some(size), None) | (None, Some(size)) => Units::Pixels(val / 100.0 * size),
+                (None, None) => *self,
+            },
+            units => units,
+        };
+
+        match units {
+            Units::Pixels(val) => Units::Pixels(context.numeric_mode.quantize(val)),
+            units => units,
         }
     }
 
@@ -185,6 +289,29 @@ impl Units {
     pub fn is_auto(&self) -> bool {
         self == &Units::Auto
     }
+
+    /// Returns true if the value is relative to the viewport.
+    pub fn is_viewport(&self) -> bool {
+        matches!(
+            self,
+            Units::ViewportWidth(_) | Units::ViewportHeight(_) | Units::ViewportMin(_) | Units::ViewportMax(_)
+        )
+    }
+
+    /// Returns true if the value is an intrinsic sizing keyword.
+    pub fn is_intrinsic(&self) -> bool {
+        matches!(self, Units::MinContent | Units::MaxContent | Units::FitContent)
+    }
+
+    /// Returns true if the value is relative to the size of the node itself.
+    pub fn is_self_relative(&self) -> bool {
+        matches!(self, Units::SelfWidth(_) | Units::SelfHeight(_) | Units::SelfMin(_) | Units::SelfMax(_))
+    }
+
+    /// Returns true if the value is relative to a font size.
+    pub fn is_font_relative(&self) -> bool {
+        matches!(self, Units::Em(_) | Units::Rem(_))
+    }
 }
 
 impl std::fmt::Display for Units {
@@ -194,6 +321,19 @@ impl std::fmt::Display for Units {
             Units::Pixels(p) => write!(f, "{}px", p),
             Units::Percentage(p) => write!(f, "{}%", p),
             Units::Stretch(s) => write!(f, "{}s", s),
+            Units::ViewportWidth(v) => write!(f, "{}vw", v),
+            Units::ViewportHeight(v) => write!(f, "{}vh", v),
+            Units::ViewportMin(v) => write!(f, "{}vmin", v),
+            Units::ViewportMax(v) => write!(f, "{}vmax", v),
+            Units::Em(v) => write!(f, "{}em", v),
+            Units::Rem(v) => write!(f, "{}rem", v),
+            Units::SelfWidth(v) => write!(f, "{}sw", v),
+            Units::SelfHeight(v) => write!(f, "{}sh", v),
+            Units::SelfMin(v) => write!(f, "{}smin", v),
+            Units::SelfMax(v) => write!(f, "{}smax", v),
+            Units::MinContent => write!(f, "min-content"),
+            Units::MaxContent => write!(f, "max-content"),
+            Units::FitContent => write!(f, "fit-content"),
         }
     }
 }
@@ -251,11 +391,395 @@ impl std::fmt::Display for LayoutWrap {
     }
 }
 
+/// The overflow mode of a node on one axis, which determines whether the content of the node is clipped to its
+/// padding box.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Content is not clipped and may be visible outside the node (default).
+    #[default]
+    Visible,
+    /// Content is clipped to the padding box of the node.
+    Hidden,
+    /// Content is clipped to the padding box of the node and can be scrolled.
+    Scroll,
+}
+
+impl Overflow {
+    /// Returns true if the content of the node is clipped.
+    pub fn is_clipped(&self) -> bool {
+        matches!(self, Overflow::Hidden | Overflow::Scroll)
+    }
+}
+
+impl std::fmt::Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Overflow::Visible => write!(f, "visible"),
+            Overflow::Hidden => write!(f, "hidden"),
+            Overflow::Scroll => write!(f, "scroll"),
+        }
+    }
+}
+
+/// The numeric mode of layout, which determines how computed values are rounded.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumericMode {
+    /// Computed values are floating-point (default). Stretch space is distributed in whole pixels, and other values
+    /// are left unrounded.
+    #[default]
+    Float,
+    /// Computed values are fixed-point, with the given number of fractional bits, so every value is a multiple of
+    /// `1 / 2^bits` pixels.
+    ///
+    /// Resolved units and the computed size of each node are rounded to the nearest multiple, with halfway cases
+    /// rounded away from zero, and stretch space is distributed in multiples of the step. After layout, the edges of
+    /// every node are rounded to the fixed-point grid in the same way as with [`PixelSnapping::Edges`].
+    ///
+    /// The values are still stored as [`Scalar`], so the number of fractional bits is limited to
+    /// [`MAX_FRACTION_BITS`](NumericMode::MAX_FRACTION_BITS), which leaves at least 16 bits of the mantissa for the
+    /// integer part. Values on the grid within [`FIXED_POINT_RANGE`](NumericMode::FIXED_POINT_RANGE) pixels of the origin are
+    /// then exactly representable, and sums and differences of them within that range are exact, so the edges and
+    /// sizes of nodes in that range do not depend on their position. Beyond that range the values are rounded by the
+    /// floating-point type as well.
+    FixedPoint {
+        /// The number of fractional bits, such that a value of 8 gives a step of 1/256 of a pixel. Larger values are
+        /// treated as [`MAX_FRACTION_BITS`](NumericMode::MAX_FRACTION_BITS).
+        fraction_bits: u8,
+    },
+    /// Computed values are whole character cells, for terminal user interfaces.
+    ///
+    /// This behaves as [`NumericMode::FixedPoint`] with no fractional bits, so that the position and size of every node
+    /// reported to the [`Cache`](crate::Cache) are integers. Leftover cells of stretch space are given one at a time to the
+    /// items with the largest fractional share, with ties going to the earlier item, and content sizes are rounded up so
+    /// that content is never truncated.
+    Cells,
+}
+
+impl NumericMode {
+    /// The largest number of fractional bits of [`NumericMode::FixedPoint`], which is 8 with `f32` and 32 with `f64`.
+    pub const MAX_FRACTION_BITS: u8 = {
+        let bits = Scalar::MANTISSA_DIGITS - 16;
+        if bits > 32 {
+            32
+        } else {
+            bits as u8
+        }
+    };
+
+    /// The distance from the origin, in pixels, within which fixed-point values are exact.
+    pub const FIXED_POINT_RANGE: Scalar = 65536.0;
+
+    /// Returns the step which values are rounded to, or `None` if values are left unrounded.
+    pub fn step(&self) -> Option<Scalar> {
+        match self {
+            NumericMode::Float => None,
+            NumericMode::FixedPoint { fraction_bits } => {
+                Some(1.0 / (1u64 << (*fraction_bits).min(Self::MAX_FRACTION_BITS)) as Scalar)
+            }
+            NumericMode::Cells => Some(1.0),
+        }
+    }
+
+    /// Rounds the given value to the nearest multiple of the [`step`](NumericMode::step) of the mode.
+    pub fn quantize(&self, value: Scalar) -> Scalar {
+        match self.step() {
+            Some(step) => (value / step).round() * step,
+            None => value,
+        }
+    }
+
+    /// Rounds the given value to the nearest multiple of the [`step`](NumericMode::step) of the mode, or to the
+    /// nearest whole pixel for [`NumericMode::Float`].
+    pub(crate) fn round(&self, value: Scalar) -> Scalar {
+        match self {
+            NumericMode::Float => value.round(),
+            mode => mode.quantize(value),
+        }
+    }
+
+    /// Rounds the given content size up to a whole number of cells for [`NumericMode::Cells`].
+    pub(crate) fn round_content(&self, value: Scalar) -> Scalar {
+        match self {
+            NumericMode::Cells => value.ceil(),
+            _ => value,
+        }
+    }
+}
+
+impl std::fmt::Display for NumericMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumericMode::Float => write!(f, "float"),
+            NumericMode::FixedPoint { fraction_bits } => write!(f, "fixed-point({fraction_bits})"),
+            NumericMode::Cells => write!(f, "cells"),
+        }
+    }
+}
+
+/// Determines whether the computed bounds of nodes are rounded to the device pixel grid after layout.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelSnapping {
+    /// Bounds are left unrounded (default).
+    #[default]
+    None,
+    /// The edges of each node are rounded to the nearest device pixel, and sizes are derived from the rounded edges,
+    /// so that adjacent nodes which share an edge before rounding still share an edge afterwards.
+    Edges,
+}
+
+impl std::fmt::Display for PixelSnapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PixelSnapping::None => write!(f, "none"),
+            PixelSnapping::Edges => write!(f, "edges"),
+        }
+    }
+}
+
+/// The direction in which to move focus with [`navigate`](crate::navigate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusDirection {
+    /// Move focus to the left.
+    Left,
+    /// Move focus to the right.
+    Right,
+    /// Move focus up.
+    Up,
+    /// Move focus down.
+    Down,
+}
+
+impl std::fmt::Display for FocusDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FocusDirection::Left => write!(f, "left"),
+            FocusDirection::Right => write!(f, "right"),
+            FocusDirection::Up => write!(f, "up"),
+            FocusDirection::Down => write!(f, "down"),
+        }
+    }
+}
+
+/// The placement of an anchored node relative to its [`anchor`](crate::Node::anchor).
+///
+/// The first part of each variant names the side of the anchor on which the node is placed, and the second part the
+/// alignment of the node along that side, where the start is the left or top edge of the anchor.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// Above the anchor, aligned to its left edge.
+    AboveStart,
+    /// Above the anchor, centered horizontally.
+    AboveCenter,
+    /// Above the anchor, aligned to its right edge.
+    AboveEnd,
+    /// Below the anchor, aligned to its left edge (default).
+    #[default]
+    BelowStart,
+    /// Below the anchor, centered horizontally.
+    BelowCenter,
+    /// Below the anchor, aligned to its right edge.
+    BelowEnd,
+    /// Left of the anchor, aligned to its top edge.
+    LeftStart,
+    /// Left of the anchor, centered vertically.
+    LeftCenter,
+    /// Left of the anchor, aligned to its bottom edge.
+    LeftEnd,
+    /// Right of the anchor, aligned to its top edge.
+    RightStart,
+    /// Right of the anchor, centered vertically.
+    RightCenter,
+    /// Right of the anchor, aligned to its bottom edge.
+    RightEnd,
+}
+
+impl Placement {
+    /// Returns the placement on the opposite side of the anchor with the same alignment.
+    pub fn flip(&self) -> Self {
+        match self {
+            Placement::AboveStart => Placement::BelowStart,
+            Placement::AboveCenter => Placement::BelowCenter,
+            Placement::AboveEnd => Placement::BelowEnd,
+            Placement::BelowStart => Placement::AboveStart,
+            Placement::BelowCenter => Placement::AboveCenter,
+            Placement::BelowEnd => Placement::AboveEnd,
+            Placement::LeftStart => Placement::RightStart,
+            Placement::LeftCenter => Placement::RightCenter,
+            Placement::LeftEnd => Placement::RightEnd,
+            Placement::RightStart => Placement::LeftStart,
+            Placement::RightCenter => Placement::LeftCenter,
+            Placement::RightEnd => Placement::LeftEnd,
+        }
+    }
+
+    /// Returns true if the node is placed above or below the anchor.
+    pub fn is_vertical(&self) -> bool {
+        matches!(
+            self,
+            Placement::AboveStart
+                | Placement::AboveCenter
+                | Placement::AboveEnd
+                | Placement::BelowStart
+                | Placement::BelowCenter
+                | Placement::BelowEnd
+        )
+    }
+}
+
+impl std::fmt::Display for Placement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Placement::AboveStart => write!(f, "above-start"),
+            Placement::AboveCenter => write!(f, "above-center"),
+            Placement::AboveEnd => write!(f, "above-end"),
+            Placement::BelowStart => write!(f, "below-start"),
+            Placement::BelowCenter => write!(f, "below-center"),
+            Placement::BelowEnd => write!(f, "below-end"),
+            Placement::LeftStart => write!(f, "left-start"),
+            Placement::LeftCenter => write!(f, "left-center"),
+            Placement::LeftEnd => write!(f, "left-end"),
+            Placement::RightStart => write!(f, "right-start"),
+            Placement::RightCenter => write!(f, "right-center"),
+            Placement::RightEnd => write!(f, "right-end"),
+        }
+    }
+}
+
 /// A type which represents the computed size of a node after [`layout`](crate::Node::layout).
 #[derive(Default, Debug, Copy, Clone, PartialEq)]
 pub struct Size {
     /// The computed size on the main axis.
-    pub main: f32,
+    pub main: Scalar,
     /// The computed size on the cross axis.
-    pub cross: f32,
+    pub cross: Scalar,
+}
+
+/// A 2D affine transform, which maps a point (`x`, `y`) to (`a * x + c * y + e`, `b * x + d * y + f`).
+///
+/// Transforms are applied to nodes after layout and do not affect the size or position of any node.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Transform {
+    /// The contribution of `x` to the transformed `x`.
+    pub a: Scalar,
+    /// The contribution of `x` to the transformed `y`.
+    pub b: Scalar,
+    /// The contribution of `y` to the transformed `x`.
+    pub c: Scalar,
+    /// The contribution of `y` to the transformed `y`.
+    pub d: Scalar,
+    /// The horizontal translation.
+    pub e: Scalar,
+    /// The vertical translation.
+    pub f: Scalar,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    /// The transform which leaves every point unchanged.
+    pub const IDENTITY: Self = Self { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 };
+
+    /// Returns a transform which translates by `x` and `y`.
+    pub fn translate(x: Scalar, y: Scalar) -> Self {
+        Self { e: x, f: y, ..Self::IDENTITY }
+    }
+
+    /// Returns a transform which scales by `x` and `y`.
+    pub fn scale(x: Scalar, y: Scalar) -> Self {
+        Self { a: x, d: y, ..Self::IDENTITY }
+    }
+
+    /// Returns a transform which rotates clockwise by `angle` radians, with the y axis pointing down.
+    pub fn rotate(angle: Scalar) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self { a: cos, b: sin, c: -sin, d: cos, ..Self::IDENTITY }
+    }
+
+    /// Returns the transform which applies this transform followed by `other`.
+    pub fn then(&self, other: &Transform) -> Self {
+        Self {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f,
+        }
+    }
+
+    /// Returns the inverse of the transform, or `None` if the transform cannot be inverted.
+    pub fn invert(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            e: (self.c * self.f - self.d * self.e) / det,
+            f: (self.b * self.e - self.a * self.f) / det,
+        })
+    }
+
+    /// Returns true if the transform maps horizontal and vertical lines to horizontal and vertical lines.
+    pub fn is_axis_aligned(&self) -> bool {
+        self.b == 0.0 && self.c == 0.0
+    }
+
+    /// Applies the transform to a point.
+    pub fn apply(&self, x: Scalar, y: Scalar) -> (Scalar, Scalar) {
+        (self.a * x + self.c * y + self.e, self.b * x + self.d * y + self.f)
+    }
+
+    /// Returns the position and size of the smallest axis-aligned rectangle containing the transformed rectangle.
+    pub fn bounding_box(
+        &self,
+        posx: Scalar,
+        posy: Scalar,
+        width: Scalar,
+        height: Scalar,
+    ) -> (Scalar, Scalar, Scalar, Scalar) {
+        let corners = [
+            self.apply(posx, posy),
+            self.apply(posx + width, posy),
+            self.apply(posx, posy + height),
+            self.apply(posx + width, posy + height),
+        ];
+
+        let (mut left, mut top, mut right, mut bottom) = (Scalar::MAX, Scalar::MAX, Scalar::MIN, Scalar::MIN);
+        for (x, y) in corners {
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x);
+            bottom = bottom.max(y);
+        }
+
+        (left, top, right - left, bottom - top)
+    }
+}
+
+/// The reference values used to [`resolve`](Units::resolve) units which are not relative to the parent of a node.
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct UnitContext {
+    /// The width of the viewport, i.e. the computed width of the root node passed to [`layout`](crate::Node::layout).
+    pub viewport_width: Scalar,
+    /// The height of the viewport, i.e. the computed height of the root node passed to [`layout`](crate::Node::layout).
+    pub viewport_height: Scalar,
+    /// The font size used to resolve [`Units::Em`].
+    pub font_size: Scalar,
+    /// The font size of the root node, used to resolve [`Units::Rem`].
+    pub root_font_size: Scalar,
+    /// The computed width of the node, if known, used to resolve self-relative units.
+    pub width: Option<Scalar>,
+    /// The computed height of the node, if known, used to resolve self-relative units.
+    pub height: Option<Scalar>,
+    /// The numeric mode of the layout, used to round resolved values.
+    pub numeric_mode: NumericMode,
 }
@@ -1,4 +1,7 @@
-use crate::{Cache, Node};
+use crate::{
+    layout::{local_transform, visit_paint_order},
+    Cache, FocusDirection, Node, Overflow, Scalar, Transform,
+};
 
 /// Prints a debug representation of the computed layout for a tree of nodes, starting with the given root node.
 pub fn print_node<N: Node>(
@@ -44,3 +47,245 @@ pub fn print_node<N: Node>(
         print_node(child, cache, tree, false, has_sibling, new_string.clone());
     }
 }
+
+/// Records the visible nodes containing the point, in tree order, given the transform from the coordinate space of the
+/// parent of `node` to the coordinate space of the root.
+fn collect_hits<'t, N: Node>(
+    node: &'t N,
+    parent: Transform,
+    cache: &impl Cache<Node = N>,
+    tree: &'t N::Tree,
+    store: &N::Store,
+    point: (Scalar, Scalar),
+    hits: &mut Vec<&'t N>,
+) {
+    if !node.visible(store) {
+        return;
+    }
+
+    let transform = local_transform(node, cache, store).then(&parent);
+
+    // The clip of the node is in the coordinate space of its parent.
+    let clipped = cache.clip(node).is_some_and(|(posx, posy, width, height)| {
+        parent
+            .invert()
+            .map(|inverse| inverse.apply(point.0, point.1))
+            .is_none_or(|(x, y)| x < posx || x >= posx + width || y < posy || y >= posy + height)
+    });
+
+    // Test the point in the coordinate space of the node, so that transformed nodes are hit where they are painted.
+    if let Some((x, y)) = transform.invert().map(|inverse| inverse.apply(point.0, point.1)) {
+        if !clipped && x >= 0.0 && x < cache.width(node) && y >= 0.0 && y < cache.height(node) {
+            hits.push(node);
+        }
+    }
+
+    for child in node.children(tree) {
+        collect_hits(child, transform, cache, tree, store, point, hits);
+    }
+}
+
+/// Returns all of the visible nodes containing the point (`x`, `y`) in a tree of nodes, starting with the given root
+/// node, ordered from topmost to bottommost.
+///
+/// The point is in the coordinate space of the root node, and the parent-relative positions in the cache are
+/// accumulated down the tree, along with the [`transform`](crate::Node::transform) of each node. Hidden nodes and their
+/// descendants are skipped, as are points outside of the [clip](crate::Cache::clip) of a node, if the cache provides
+/// it. Nodes are ordered by their paint order,
+/// so a node with a greater [`z_index`](crate::Node::z_index) is above the nodes it overlaps.
+pub fn hit_test_all<'t, N: Node>(
+    node: &'t N,
+    cache: &impl Cache<Node = N>,
+    tree: &'t N::Tree,
+    store: &N::Store,
+    x: Scalar,
+    y: Scalar,
+) -> Vec<&'t N> {
+    let mut hits = Vec::new();
+    let root = Transform::translate(-cache.posx(node), -cache.posy(node));
+    collect_hits(node, root, cache, tree, store, (x, y), &mut hits);
+
+    let mut ordered = Vec::with_capacity(hits.len());
+    visit_paint_order(node, tree, store, &mut |node| {
+        if hits.iter().any(|hit| hit.key() == node.key()) {
+            ordered.push(node);
+        }
+    });
+
+    ordered.reverse();
+    ordered
+}
+
+/// Returns the topmost visible node containing the point (`x`, `y`) in a tree of nodes, starting with the given root
+/// node, or `None` if the point is outside of every node.
+///
+/// See [`hit_test_all`] for how the point is tested.
+pub fn hit_test<'t, N: Node>(
+    node: &'t N,
+    cache: &impl Cache<Node = N>,
+    tree: &'t N::Tree,
+    store: &N::Store,
+    x: Scalar,
+    y: Scalar,
+) -> Option<&'t N> {
+    hit_test_all(node, cache, tree, store, x, y).into_iter().next()
+}
+
+/// A rectangle relative to the root node, used for spatial navigation.
+#[derive(Debug, Clone, Copy)]
+struct NavRect {
+    left: Scalar,
+    top: Scalar,
+    right: Scalar,
+    bottom: Scalar,
+}
+
+impl NavRect {
+    fn intersects(&self, other: &NavRect) -> bool {
+        self.left < other.right && other.left < self.right && self.top < other.bottom && other.top < self.bottom
+    }
+
+    fn intersection(&self, other: &NavRect) -> NavRect {
+        NavRect {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right).max(self.left.max(other.left)),
+            bottom: self.bottom.min(other.bottom).max(self.top.max(other.top)),
+        }
+    }
+
+    // Returns the rectangle with the axes swapped and mirrored, such that `direction` becomes `FocusDirection::Right`.
+    fn oriented(&self, direction: FocusDirection) -> NavRect {
+        match direction {
+            FocusDirection::Right => *self,
+            FocusDirection::Left => {
+                NavRect { left: -self.right, top: self.top, right: -self.left, bottom: self.bottom }
+            }
+            FocusDirection::Down => NavRect { left: self.top, top: self.left, right: self.bottom, bottom: self.right },
+            FocusDirection::Up => NavRect { left: -self.bottom, top: self.left, right: -self.top, bottom: self.right },
+        }
+    }
+}
+
+/// A node along with its bounds relative to the root and the index of its nearest scroll container.
+struct NavCandidate<'t, N> {
+    node: &'t N,
+    rect: NavRect,
+    container: Option<usize>,
+    in_view: bool,
+}
+
+/// The nodes and scroll containers recorded for spatial navigation.
+struct NavState<'t, N> {
+    // The visible area of each scroll container.
+    containers: Vec<NavRect>,
+    // The visible focusable nodes.
+    candidates: Vec<NavCandidate<'t, N>>,
+    // The node which focus is moving from.
+    origin: Option<NavCandidate<'t, N>>,
+}
+
+/// Records the visible focusable nodes and scroll containers in the subtree of `node`, given the transform from the
+/// coordinate space of the parent of `node` to the coordinate space of the root, and the nearest scroll container
+/// above `node`.
+#[allow(clippy::too_many_arguments)]
+fn collect_focusable<'t, N: Node>(
+    node: &'t N,
+    from: &N::CacheKey,
+    parent: Transform,
+    container: Option<usize>,
+    cache: &impl Cache<Node = N>,
+    tree: &'t N::Tree,
+    store: &N::Store,
+    state: &mut NavState<'t, N>,
+) {
+    if !node.visible(store) {
+        return;
+    }
+
+    let transform = local_transform(node, cache, store).then(&parent);
+    let (posx, posy, width, height) = transform.bounding_box(0.0, 0.0, cache.width(node), cache.height(node));
+    let rect = NavRect { left: posx, top: posy, right: posx + width, bottom: posy + height };
+    let in_view = container.is_none_or(|index| rect.intersects(&state.containers[index]));
+
+    if node.key() == *from {
+        state.origin = Some(NavCandidate { node, rect, container, in_view });
+    } else if node.focusable(store).unwrap_or_default() && width > 0.0 && height > 0.0 {
+        state.candidates.push(NavCandidate { node, rect, container, in_view });
+    }
+
+    // A scroll container limits the visible area of its descendants to its bounds.
+    let is_scroll_container = node.horizontal_overflow(store).unwrap_or_default() == Overflow::Scroll
+        || node.vertical_overflow(store).unwrap_or_default() == Overflow::Scroll;
+
+    let container = if is_scroll_container {
+        let visible = container.map_or(rect, |index| state.containers[index].intersection(&rect));
+        state.containers.push(visible);
+        Some(state.containers.len() - 1)
+    } else {
+        container
+    };
+
+    for child in node.children(tree) {
+        collect_focusable(child, from, transform, container, cache, tree, store, state);
+    }
+}
+
+/// Returns the best focusable node to move focus to from the node `from` in the given direction, within a tree of
+/// nodes starting with the given root node, or `None` if there is no focusable node in that direction.
+///
+/// A candidate is a visible node with [`focusable`](crate::Node::focusable) set, whose center lies beyond the far edge
+/// of `from` in the given direction. Candidates which overlap `from` on the perpendicular axis are preferred, followed
+/// by the distance between the nodes, where a perpendicular offset counts double. Bounds are computed in the same way
+/// as for [`hit_test`], including scroll offsets and transforms.
+///
+/// Candidates in the same scroll container as `from` are preferred, so that focus moves through a scrolled list
+/// before leaving it, even to nodes which are scrolled out of view. Candidates in other scroll containers must be at
+/// least partly within the visible area of their container.
+pub fn navigate<'t, N: Node>(
+    node: &'t N,
+    from: &N,
+    direction: FocusDirection,
+    cache: &impl Cache<Node = N>,
+    tree: &'t N::Tree,
+    store: &N::Store,
+) -> Option<&'t N> {
+    let mut state = NavState { containers: Vec::new(), candidates: Vec::new(), origin: None };
+    let root = Transform::translate(-cache.posx(node), -cache.posy(node));
+    collect_focusable(node, &from.key(), root, None, cache, tree, store, &mut state);
+
+    let origin = state.origin?;
+    let from_rect = origin.rect.oriented(direction);
+
+    // Candidates are compared by whether they do not overlap `from` on the perpendicular axis, and then by distance.
+    let score = |candidate: &NavCandidate<'t, N>| {
+        let rect = candidate.rect.oriented(direction);
+        let distance = (rect.left - from_rect.right).max(0.0);
+        let perpendicular = (rect.top - from_rect.bottom).max(from_rect.top - rect.bottom).max(0.0);
+        let overlaps = rect.top < from_rect.bottom && from_rect.top < rect.bottom;
+        (!overlaps, distance + 2.0 * perpendicular)
+    };
+
+    let in_direction = |candidate: &&NavCandidate<'t, N>| {
+        let rect = candidate.rect.oriented(direction);
+        (rect.left + rect.right) / 2.0 > from_rect.right
+    };
+
+    // Ties are resolved in tree order.
+    let best = |candidates: Vec<&NavCandidate<'t, N>>| {
+        candidates
+            .into_iter()
+            .map(|candidate| (candidate, score(candidate)))
+            .reduce(|best, next| if next.1 < best.1 { next } else { best })
+            .map(|(candidate, _)| candidate.node)
+    };
+
+    let candidates = state.candidates.iter().filter(in_direction);
+
+    let same_container = candidates
+        .clone()
+        .filter(|candidate| origin.container.is_some() && candidate.container == origin.container)
+        .collect();
+
+    best(same_container).or_else(|| best(candidates.filter(|candidate| candidate.in_view).collect()))
+}
@@ -353,3 +353,67 @@ fn rtl_absolute_in_column_only_flips_horizontal_offsets() {
     // RTL mirrors only horizontal offsets, so left becomes trailing while top is unchanged.
     assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 480.0, posy: 10.0, width: 100.0, height: 80.0 }));
 }
+
+#[test]
+fn auto_absolute_fills_between_insets() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_position_type(node, PositionType::Absolute);
+    world.set_width(node, Units::Auto);
+    world.set_height(node, Units::Auto);
+    world.set_left(node, Units::Pixels(50.0));
+    world.set_right(node, Units::Pixels(100.0));
+    world.set_top(node, Units::Percentage(25.0));
+    world.set_bottom(node, Units::Percentage(50.0));
+
+    let child = world.add(Some(node));
+    world.set_width(child, Units::Stretch(1.0));
+    world.set_height(child, Units::Pixels(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 50.0, posy: 150.0, width: 450.0, height: 150.0 }));
+    assert_eq!(world.cache.bounds(child), Some(&Rect { posx: 0.0, posy: 0.0, width: 450.0, height: 50.0 }));
+
+    world.set_layout_type(root, LayoutType::Row);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 50.0, posy: 150.0, width: 450.0, height: 150.0 }));
+
+    world.set_layout_type(root, LayoutType::Overlay);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 50.0, posy: 150.0, width: 450.0, height: 150.0 }));
+}
+
+#[test]
+fn auto_absolute_with_one_inset_is_content_sized() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_position_type(node, PositionType::Absolute);
+    world.set_width(node, Units::Auto);
+    world.set_height(node, Units::Auto);
+    world.set_right(node, Units::Pixels(100.0));
+    world.set_top(node, Units::Pixels(20.0));
+    world.set_bottom(node, Units::Stretch(1.0));
+
+    let child = world.add(Some(node));
+    world.set_width(child, Units::Pixels(80.0));
+    world.set_height(child, Units::Pixels(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 420.0, posy: 20.0, width: 80.0, height: 50.0 }));
+}
@@ -0,0 +1,206 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+fn button(world: &mut World, parent: Entity, left: Scalar, top: Scalar) -> Entity {
+    let button = world.add(Some(parent));
+    world.set_width(button, Units::Pixels(100.0));
+    world.set_height(button, Units::Pixels(40.0));
+    world.set_position_type(button, PositionType::Absolute);
+    world.set_left(button, Units::Pixels(left));
+    world.set_top(button, Units::Pixels(top));
+    button
+}
+
+fn menu(world: &mut World, parent: Entity, anchor: Entity, placement: Placement) -> Entity {
+    let menu = world.add(Some(parent));
+    world.set_width(menu, Units::Pixels(150.0));
+    world.set_height(menu, Units::Pixels(200.0));
+    world.set_position_type(menu, PositionType::Absolute);
+    world.set_anchor(menu, anchor);
+    world.set_placement(menu, placement);
+    menu
+}
+
+#[test]
+fn anchor_below_start() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let button = button(&mut world, root, 100.0, 50.0);
+    let menu = menu(&mut world, root, button, Placement::BelowStart);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(menu), Some(&Rect { posx: 100.0, posy: 90.0, width: 150.0, height: 200.0 }));
+}
+
+#[test]
+fn anchor_placements() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let button = button(&mut world, root, 250.0, 250.0);
+    let menu = menu(&mut world, root, button, Placement::AboveEnd);
+
+    let expected = [
+        (Placement::AboveStart, 250.0, 50.0),
+        (Placement::AboveCenter, 225.0, 50.0),
+        (Placement::AboveEnd, 200.0, 50.0),
+        (Placement::BelowStart, 250.0, 290.0),
+        (Placement::BelowCenter, 225.0, 290.0),
+        (Placement::BelowEnd, 200.0, 290.0),
+        (Placement::LeftStart, 100.0, 250.0),
+        (Placement::LeftCenter, 100.0, 170.0),
+        (Placement::LeftEnd, 100.0, 90.0),
+        (Placement::RightStart, 350.0, 250.0),
+        (Placement::RightCenter, 350.0, 170.0),
+        (Placement::RightEnd, 350.0, 90.0),
+    ];
+
+    for (placement, posx, posy) in expected {
+        world.set_placement(menu, placement);
+
+        root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+        assert_eq!(
+            world.cache.bounds(menu),
+            Some(&Rect { posx, posy, width: 150.0, height: 200.0 }),
+            "placement {placement}"
+        );
+    }
+}
+
+#[test]
+fn anchor_flips_when_overflowing() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let button = button(&mut world, root, 100.0, 500.0);
+    let menu = menu(&mut world, root, button, Placement::BelowStart);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(menu), Some(&Rect { posx: 100.0, posy: 300.0, width: 150.0, height: 200.0 }));
+
+    // The menu does not fit on either side of the anchor, so stays on the side with more space.
+    world.set_height(menu, Units::Pixels(550.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(menu), Some(&Rect { posx: 100.0, posy: -50.0, width: 150.0, height: 550.0 }));
+
+    world.set_left(button, Units::Pixels(500.0));
+    world.set_top(button, Units::Pixels(100.0));
+    world.set_height(menu, Units::Pixels(200.0));
+    world.set_placement(menu, Placement::RightStart);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(menu), Some(&Rect { posx: 350.0, posy: 100.0, width: 150.0, height: 200.0 }));
+}
+
+#[test]
+fn anchor_shifts_within_viewport() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let button = button(&mut world, root, 520.0, 50.0);
+    let menu = menu(&mut world, root, button, Placement::BelowStart);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(menu), Some(&Rect { posx: 450.0, posy: 90.0, width: 150.0, height: 200.0 }));
+
+    world.set_top(button, Units::Pixels(500.0));
+    world.set_placement(menu, Placement::RightCenter);
+    world.set_left(button, Units::Pixels(0.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(menu), Some(&Rect { posx: 100.0, posy: 400.0, width: 150.0, height: 200.0 }));
+}
+
+#[test]
+fn anchor_in_other_subtree() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let toolbar = world.add(Some(root));
+    world.set_width(toolbar, Units::Stretch(1.0));
+    world.set_height(toolbar, Units::Pixels(100.0));
+    world.set_vertical_scroll(toolbar, -20.0);
+
+    let button = button(&mut world, toolbar, 100.0, 50.0);
+
+    let container = world.add(Some(root));
+    world.set_width(container, Units::Pixels(200.0));
+    world.set_height(container, Units::Pixels(200.0));
+    world.set_left(container, Units::Pixels(200.0));
+
+    let menu = menu(&mut world, container, button, Placement::BelowStart);
+
+    let item = world.add(Some(menu));
+    world.set_width(item, Units::Stretch(1.0));
+    world.set_height(item, Units::Pixels(30.0));
+    world.set_top(item, Units::Pixels(10.0));
+
+    let submenu = self::menu(&mut world, root, item, Placement::RightStart);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The button is at (100, 90) relative to the root, and the container at (200, 100).
+    assert_eq!(world.cache.bounds(menu), Some(&Rect { posx: -100.0, posy: -10.0, width: 150.0, height: 200.0 }));
+    assert_eq!(world.cache.bounds(item), Some(&Rect { posx: 0.0, posy: 10.0, width: 150.0, height: 30.0 }));
+
+    // The submenu is placed next to the item after the menu has been moved.
+    assert_eq!(world.cache.bounds(submenu), Some(&Rect { posx: 250.0, posy: 100.0, width: 150.0, height: 200.0 }));
+}
+
+#[test]
+fn anchor_in_sticky_node() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(400.0));
+    world.set_height(root, Units::Pixels(400.0));
+    world.set_vertical_overflow(root, Overflow::Scroll);
+    world.set_vertical_scroll(root, -150.0);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(100.0));
+    world.set_height(node, Units::Pixels(100.0));
+
+    let header = world.add(Some(root));
+    world.set_width(header, Units::Stretch(1.0));
+    world.set_height(header, Units::Pixels(50.0));
+    world.set_position_type(header, PositionType::Sticky);
+    world.set_top(header, Units::Pixels(10.0));
+
+    let content = world.add(Some(root));
+    world.set_width(content, Units::Pixels(100.0));
+    world.set_height(content, Units::Pixels(1000.0));
+
+    // The menu is placed against the anchor once the header has been pinned to the top of the root.
+    let button = button(&mut world, header, 100.0, 5.0);
+    let menu = menu(&mut world, root, button, Placement::BelowStart);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(header), Some(&Rect { posx: 0.0, posy: 10.0, width: 400.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(menu), Some(&Rect { posx: 100.0, posy: 55.0, width: 150.0, height: 200.0 }));
+}
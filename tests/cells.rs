@@ -0,0 +1,93 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+// Simulates a single line of text which is 7.2 cells wide.
+fn text(_: &Store, _: Option<Scalar>, _: Option<Scalar>) -> (Scalar, Scalar) {
+    (7.2, 1.0)
+}
+
+#[test]
+fn cells_distribute_leftover_stretch() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(10.0));
+    world.set_height(root, Units::Pixels(10.0));
+    world.set_layout_type(root, LayoutType::Row);
+    world.set_numeric_mode(root, NumericMode::Cells);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Stretch(1.0));
+    world.set_height(node1, Units::Pixels(1.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Stretch(2.0));
+    world.set_height(node2, Units::Pixels(1.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // Exact shares of 3.33 and 6.67 cells, so the leftover cell goes to the second node.
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: 0.0, width: 3.0, height: 1.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 3.0, posy: 0.0, width: 7.0, height: 1.0 }));
+}
+
+#[test]
+fn cells_round_percentages_and_alignment() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(15.0));
+    world.set_height(root, Units::Pixels(10.0));
+    world.set_alignment(root, Alignment::Center);
+    world.set_numeric_mode(root, NumericMode::Cells);
+
+    // A width of 7.5 cells is rounded to 8, and the node is centered in the 7 remaining cells.
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Percentage(50.0));
+    world.set_height(node, Units::Pixels(5.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 4.0, posy: 3.0, width: 8.0, height: 5.0 }));
+}
+
+#[test]
+fn cells_round_content_size_up() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(20.0));
+    world.set_height(root, Units::Pixels(10.0));
+    world.set_numeric_mode(root, NumericMode::Cells);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Auto);
+    world.set_height(node, Units::Auto);
+    world.set_content_size(node, text);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 8.0, height: 1.0 }));
+}
+
+#[test]
+fn cells_ignore_pixel_snapping() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(10.0));
+    world.set_height(root, Units::Pixels(10.0));
+    world.set_alignment(root, Alignment::Center);
+    world.set_numeric_mode(root, NumericMode::Cells);
+    world.set_pixel_snapping(root, PixelSnapping::Edges);
+    world.set_scale_factor(root, 1.2);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(1.0));
+    world.set_height(node, Units::Pixels(1.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The node spans 4.5 to 5.5 cells, which is rounded to whole cells as if pixel snapping were disabled.
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 5.0, posy: 5.0, width: 1.0, height: 1.0 }));
+}
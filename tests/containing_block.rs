@@ -0,0 +1,166 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn absolute_without_containing_block() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(400.0));
+    world.set_height(node1, Units::Pixels(400.0));
+    world.set_left(node1, Units::Pixels(50.0));
+    world.set_top(node1, Units::Pixels(50.0));
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(200.0));
+    world.set_height(node2, Units::Pixels(200.0));
+
+    let node3 = world.add(Some(node2));
+    world.set_width(node3, Units::Pixels(50.0));
+    world.set_height(node3, Units::Pixels(50.0));
+    world.set_position_type(node3, PositionType::Absolute);
+    world.set_right(node3, Units::Pixels(0.0));
+    world.set_bottom(node3, Units::Pixels(0.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node3), Some(&Rect { posx: 150.0, posy: 150.0, width: 50.0, height: 50.0 }));
+}
+
+#[test]
+fn absolute_against_containing_block() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(400.0));
+    world.set_height(node1, Units::Pixels(400.0));
+    world.set_left(node1, Units::Pixels(50.0));
+    world.set_top(node1, Units::Pixels(50.0));
+    world.set_border(node1, Units::Pixels(10.0));
+    world.set_containing_block(node1, true);
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(200.0));
+    world.set_height(node2, Units::Pixels(200.0));
+    world.set_left(node2, Units::Pixels(20.0));
+    world.set_top(node2, Units::Pixels(20.0));
+
+    let node3 = world.add(Some(node2));
+    world.set_width(node3, Units::Percentage(25.0));
+    world.set_height(node3, Units::Pixels(50.0));
+    world.set_position_type(node3, PositionType::Absolute);
+    world.set_right(node3, Units::Pixels(0.0));
+    world.set_bottom(node3, Units::Pixels(0.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The padding box of the containing block spans from 10 to 390, and node2 is at (30, 30) within it.
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 30.0, posy: 30.0, width: 200.0, height: 200.0 }));
+    assert_eq!(world.cache.bounds(node3), Some(&Rect { posx: 265.0, posy: 310.0, width: 95.0, height: 50.0 }));
+}
+
+#[test]
+fn containing_block_ignores_intermediate_scroll() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_containing_block(root, true);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(300.0));
+    world.set_height(node1, Units::Pixels(300.0));
+    world.set_vertical_scroll(node1, -100.0);
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(400.0));
+
+    let node3 = world.add(Some(node2));
+    world.set_width(node3, Units::Stretch(1.0));
+    world.set_height(node3, Units::Pixels(50.0));
+    world.set_position_type(node3, PositionType::Absolute);
+    world.set_left(node3, Units::Pixels(10.0));
+    world.set_right(node3, Units::Pixels(10.0));
+    world.set_top(node3, Units::Pixels(10.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 0.0, posy: -100.0, width: 100.0, height: 400.0 }));
+    assert_eq!(world.cache.bounds(node3), Some(&Rect { posx: 10.0, posy: 110.0, width: 580.0, height: 50.0 }));
+}
+
+#[test]
+fn nearest_containing_block() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_containing_block(root, true);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(300.0));
+    world.set_height(node1, Units::Pixels(300.0));
+    world.set_left(node1, Units::Pixels(100.0));
+    world.set_containing_block(node1, true);
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(100.0));
+
+    let node3 = world.add(Some(node2));
+    world.set_width(node3, Units::Pixels(50.0));
+    world.set_height(node3, Units::Pixels(50.0));
+    world.set_position_type(node3, PositionType::Absolute);
+    world.set_right(node3, Units::Pixels(0.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node3), Some(&Rect { posx: 250.0, posy: 0.0, width: 50.0, height: 50.0 }));
+}
+
+#[test]
+fn stretch_in_containing_block_respects_min_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(400.0));
+    world.set_height(node1, Units::Pixels(400.0));
+    world.set_containing_block(node1, true);
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(200.0));
+    world.set_height(node2, Units::Pixels(200.0));
+
+    let node3 = world.add(Some(node2));
+    world.set_width(node3, Units::Stretch(1.0));
+    world.set_height(node3, Units::Stretch(1.0));
+    world.set_position_type(node3, PositionType::Absolute);
+    world.set_left(node3, Units::Pixels(50.0));
+    world.set_right(node3, Units::Pixels(50.0));
+    world.set_top(node3, Units::Pixels(50.0));
+    world.set_bottom(node3, Units::Pixels(50.0));
+    world.set_min_width(node3, Units::Pixels(320.0));
+    // A minimum larger than the maximum takes precedence.
+    world.set_min_height(node3, Units::Pixels(350.0));
+    world.set_max_height(node3, Units::Pixels(100.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The 300px left between the insets is below the minimum size on both axes.
+    assert_eq!(world.cache.bounds(node3), Some(&Rect { posx: 50.0, posy: 50.0, width: 320.0, height: 350.0 }));
+}
@@ -0,0 +1,152 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn fixed_does_not_take_space() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(100.0));
+    world.set_position_type(node1, PositionType::Fixed);
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(100.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 100.0 }));
+}
+
+#[test]
+fn fixed_positioned_against_root() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(300.0));
+    world.set_height(node1, Units::Pixels(300.0));
+    world.set_left(node1, Units::Pixels(100.0));
+    world.set_top(node1, Units::Pixels(50.0));
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(50.0));
+    world.set_position_type(node2, PositionType::Fixed);
+    world.set_right(node2, Units::Pixels(10.0));
+    world.set_bottom(node2, Units::Pixels(10.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The bounds are relative to the parent, which is at (100, 50) relative to the root.
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 390.0, posy: 490.0, width: 100.0, height: 50.0 }));
+}
+
+#[test]
+fn fixed_ignores_scroll_and_clip() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(300.0));
+    world.set_height(node1, Units::Pixels(300.0));
+    world.set_vertical_overflow(node1, Overflow::Scroll);
+    world.set_vertical_scroll(node1, -100.0);
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(400.0));
+
+    let tooltip = world.add(Some(node2));
+    world.set_width(tooltip, Units::Pixels(100.0));
+    world.set_height(tooltip, Units::Pixels(50.0));
+    world.set_position_type(tooltip, PositionType::Fixed);
+    world.set_right(tooltip, Units::Pixels(10.0));
+    world.set_bottom(tooltip, Units::Pixels(10.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 0.0, posy: -100.0, width: 100.0, height: 400.0 }));
+    assert_eq!(world.cache.bounds(tooltip), Some(&Rect { posx: 490.0, posy: 640.0, width: 100.0, height: 50.0 }));
+    assert_eq!(world.cache.clip(tooltip), Some(&Rect { posx: 490.0, posy: 640.0, width: 100.0, height: 50.0 }));
+}
+
+#[test]
+fn fixed_stretch_fills_root() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(100.0));
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Stretch(1.0));
+    world.set_height(node2, Units::Pixels(50.0));
+    world.set_position_type(node2, PositionType::Fixed);
+    world.set_left(node2, Units::Pixels(10.0));
+    world.set_right(node2, Units::Pixels(10.0));
+    world.set_bottom(node2, Units::Percentage(10.0));
+
+    let node3 = world.add(Some(node2));
+    world.set_width(node3, Units::Percentage(50.0));
+    world.set_height(node3, Units::Stretch(1.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 10.0, posy: 490.0, width: 580.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(node3), Some(&Rect { posx: 0.0, posy: 0.0, width: 290.0, height: 50.0 }));
+}
+
+#[test]
+fn fixed_ignores_sticky_ancestor() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(400.0));
+    world.set_height(root, Units::Pixels(400.0));
+    world.set_vertical_overflow(root, Overflow::Scroll);
+    world.set_vertical_scroll(root, -150.0);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(100.0));
+    world.set_height(node, Units::Pixels(100.0));
+
+    let header = world.add(Some(root));
+    world.set_width(header, Units::Stretch(1.0));
+    world.set_height(header, Units::Pixels(50.0));
+    world.set_position_type(header, PositionType::Sticky);
+    world.set_top(header, Units::Pixels(10.0));
+
+    let content = world.add(Some(root));
+    world.set_width(content, Units::Pixels(100.0));
+    world.set_height(content, Units::Pixels(1000.0));
+
+    let tooltip = world.add(Some(header));
+    world.set_width(tooltip, Units::Pixels(50.0));
+    world.set_height(tooltip, Units::Pixels(20.0));
+    world.set_position_type(tooltip, PositionType::Fixed);
+    world.set_left(tooltip, Units::Pixels(30.0));
+    world.set_top(tooltip, Units::Pixels(20.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The header is pinned 10px from the top of the root, while the tooltip stays 20px from the top of the root.
+    assert_eq!(world.cache.bounds(header), Some(&Rect { posx: 0.0, posy: 10.0, width: 400.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(tooltip), Some(&Rect { posx: 30.0, posy: 10.0, width: 50.0, height: 20.0 }));
+}
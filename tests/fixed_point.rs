@@ -0,0 +1,144 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn fixed_point_rounds_resolved_units() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_numeric_mode(root, NumericMode::FixedPoint { fraction_bits: 1 });
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(10.3));
+    world.set_height(node1, Units::Percentage(33.3));
+    world.set_left(node1, Units::Pixels(0.2));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(10.0));
+    world.set_height(node2, Units::Pixels(10.0));
+    world.set_top(node2, Units::Pixels(0.75));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // Values are rounded to multiples of 0.5, with halfway cases rounded away from zero.
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: 0.0, width: 10.5, height: 33.5 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 0.0, posy: 34.5, width: 10.0, height: 10.0 }));
+}
+
+#[test]
+fn fixed_point_distributes_stretch_in_steps() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_layout_type(root, LayoutType::Row);
+    world.set_numeric_mode(root, NumericMode::FixedPoint { fraction_bits: 2 });
+
+    let nodes: Vec<_> = (0..3)
+        .map(|_| {
+            let node = world.add(Some(root));
+            world.set_width(node, Units::Stretch(1.0));
+            world.set_height(node, Units::Pixels(50.0));
+            node
+        })
+        .collect();
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // 400 quarter pixels are divided into 134, 133 and 133.
+    assert_eq!(world.cache.bounds(nodes[0]), Some(&Rect { posx: 0.0, posy: 0.0, width: 33.5, height: 50.0 }));
+    assert_eq!(world.cache.bounds(nodes[1]), Some(&Rect { posx: 33.5, posy: 0.0, width: 33.25, height: 50.0 }));
+    assert_eq!(world.cache.bounds(nodes[2]), Some(&Rect { posx: 66.75, posy: 0.0, width: 33.25, height: 50.0 }));
+}
+
+#[test]
+fn fixed_point_rounds_edges() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_alignment(root, Alignment::Center);
+    world.set_numeric_mode(root, NumericMode::FixedPoint { fraction_bits: 1 });
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(10.5));
+    world.set_height(node, Units::Pixels(10.5));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The centered node spans 44.75 to 55.25, which is rounded to 45 to 55.5.
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 45.0, posy: 45.0, width: 10.5, height: 10.5 }));
+}
+
+#[test]
+fn fixed_point_rounds_snapped_edges() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_alignment(root, Alignment::Center);
+    world.set_numeric_mode(root, NumericMode::FixedPoint { fraction_bits: 1 });
+    world.set_pixel_snapping(root, PixelSnapping::Edges);
+    world.set_scale_factor(root, 1.5);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(10.5));
+    world.set_height(node, Units::Pixels(10.5));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The node spans 44.75 to 55.25, which is snapped to 44.67 to 55.33 on the device pixel grid, and then rounded
+    // to 44.5 to 55.5 on the fixed-point grid.
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 44.5, posy: 44.5, width: 11.0, height: 11.0 }));
+}
+
+#[test]
+fn numeric_mode_step() {
+    assert_eq!(NumericMode::Float.step(), None);
+    assert_eq!(NumericMode::FixedPoint { fraction_bits: 8 }.step(), Some(1.0 / 256.0));
+    assert_eq!(NumericMode::FixedPoint { fraction_bits: 8 }.quantize(1.0 / 512.0), 1.0 / 256.0);
+    assert_eq!(NumericMode::FixedPoint { fraction_bits: 8 }.quantize(-1.0 / 512.0), -1.0 / 256.0);
+    assert_eq!(NumericMode::Float.quantize(0.1), 0.1);
+    assert_eq!(
+        NumericMode::FixedPoint { fraction_bits: 64 }.step(),
+        NumericMode::FixedPoint { fraction_bits: NumericMode::MAX_FRACTION_BITS }.step()
+    );
+}
+
+#[test]
+fn fixed_point_is_exact_at_large_coordinates() {
+    let mut world = World::default();
+
+    let mode = NumericMode::FixedPoint { fraction_bits: 16 };
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(65000.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_layout_type(root, LayoutType::Row);
+    world.set_numeric_mode(root, mode);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0 / 3.0));
+    world.set_height(node1, Units::Pixels(10.0));
+
+    let node2 = world.add(Some(root));
+    world.set_left(node2, Units::Pixels(60000.1));
+    world.set_width(node2, Units::Pixels(100.0 / 3.0));
+    world.set_height(node2, Units::Pixels(10.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The fractional bits are limited so that values near the edge of the range are still on the grid, and the size
+    // of the node far from the origin is the same as the size of the node at the origin.
+    let step = mode.step().unwrap();
+    let bounds1 = *world.cache.bounds(node1).unwrap();
+    let bounds2 = *world.cache.bounds(node2).unwrap();
+    assert_eq!(bounds2.width, bounds1.width);
+    assert_eq!((bounds2.posx / step).fract(), 0.0);
+    assert_eq!(((bounds2.posx + bounds2.width) / step).fract(), 0.0);
+    assert!(bounds2.posx > 60000.0);
+}
@@ -0,0 +1,72 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn em_uses_default_font_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Em(10.0));
+    world.set_height(node, Units::Rem(5.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 160.0, height: 80.0 }));
+}
+
+#[test]
+fn em_inherits_font_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_font_size(root, 10.0);
+
+    let container = world.add(Some(root));
+    world.set_width(container, Units::Auto);
+    world.set_height(container, Units::Auto);
+    world.set_font_size(container, 20.0);
+    world.set_padding(container, Units::Em(1.0));
+    world.set_vertical_gap(container, Units::Rem(1.0));
+
+    let node1 = world.add(Some(container));
+    world.set_width(node1, Units::Em(5.0));
+    world.set_height(node1, Units::Rem(5.0));
+
+    let node2 = world.add(Some(container));
+    world.set_font_size(node2, 8.0);
+    world.set_width(node2, Units::Em(5.0));
+    world.set_height(node2, Units::Rem(5.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(container), Some(&Rect { posx: 0.0, posy: 0.0, width: 140.0, height: 150.0 }));
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 20.0, posy: 20.0, width: 100.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 20.0, posy: 80.0, width: 40.0, height: 50.0 }));
+}
+
+#[test]
+fn em_in_grid_tracks() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Grid);
+    world.set_font_size(root, 10.0);
+    world.set_grid_columns(root, vec![Units::Em(10.0), Units::Stretch(1.0)]);
+    world.set_grid_rows(root, vec![Units::Em(5.0), Units::Stretch(1.0)]);
+
+    let node = world.add(Some(root));
+    world.set_column_start(node, 1);
+    world.set_row_start(node, 1);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 100.0, posy: 50.0, width: 500.0, height: 550.0 }));
+}
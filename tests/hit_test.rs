@@ -0,0 +1,126 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn hit_test_nested() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(200.0));
+    world.set_height(node1, Units::Pixels(200.0));
+    world.set_left(node1, Units::Pixels(100.0));
+    world.set_top(node1, Units::Pixels(100.0));
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(50.0));
+    world.set_height(node2, Units::Pixels(50.0));
+    world.set_left(node2, Units::Pixels(50.0));
+    world.set_top(node2, Units::Pixels(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 175.0, 175.0), Some(&node2));
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 120.0, 280.0), Some(&node1));
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 50.0, 50.0), Some(&root));
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 700.0, 50.0), None);
+
+    assert_eq!(hit_test_all(&root, &world.cache, &world.tree, &world.store, 175.0, 175.0), vec![&node2, &node1, &root]);
+}
+
+#[test]
+fn hit_test_skips_hidden_nodes() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Overlay);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(200.0));
+    world.set_height(node1, Units::Pixels(200.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(200.0));
+    world.set_height(node2, Units::Pixels(200.0));
+
+    let node3 = world.add(Some(node2));
+    world.set_width(node3, Units::Pixels(100.0));
+    world.set_height(node3, Units::Pixels(100.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 50.0, 50.0), Some(&node3));
+
+    world.set_visibility(node2, false);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 50.0, 50.0), Some(&node1));
+}
+
+#[test]
+fn hit_test_respects_z_index_and_scroll() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let popover = world.add(Some(root));
+    world.set_width(popover, Units::Pixels(200.0));
+    world.set_height(popover, Units::Pixels(200.0));
+    world.set_position_type(popover, PositionType::Absolute);
+    world.set_z_index(popover, 1);
+
+    let list = world.add(Some(root));
+    world.set_width(list, Units::Pixels(400.0));
+    world.set_height(list, Units::Pixels(400.0));
+    world.set_vertical_scroll(list, -100.0);
+
+    let item = world.add(Some(list));
+    world.set_width(item, Units::Pixels(400.0));
+    world.set_height(item, Units::Pixels(500.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The popover is declared first but painted last.
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 100.0, 100.0), Some(&popover));
+    assert_eq!(
+        hit_test_all(&root, &world.cache, &world.tree, &world.store, 100.0, 100.0),
+        vec![&popover, &item, &list, &root]
+    );
+
+    // The item is scrolled up by 100 pixels, so starts above the list, and unclipped content can still be hit.
+    assert_eq!(hit_test_all(&root, &world.cache, &world.tree, &world.store, 300.0, -50.0), vec![&item]);
+    assert_eq!(hit_test_all(&root, &world.cache, &world.tree, &world.store, 300.0, 450.0), vec![&root]);
+}
+
+#[test]
+fn hit_test_skips_clipped_content() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(200.0));
+    world.set_height(root, Units::Pixels(200.0));
+
+    let child = world.add(Some(root));
+    world.set_width(child, Units::Pixels(400.0));
+    world.set_height(child, Units::Pixels(400.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 300.0, 300.0), Some(&child));
+
+    // The overflow of the child is clipped away by the root.
+    world.set_overflow(root, Overflow::Hidden);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 300.0, 300.0), None);
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 100.0, 100.0), Some(&child));
+}
@@ -0,0 +1,383 @@
+use std::{cell::Cell, collections::HashSet, rc::Rc};
+
+use morphorm::*;
+use morphorm_ecs::*;
+
+// Adds a text node whose content size is counted each time it is measured.
+fn add_text(world: &mut World, parent: Entity, measured: &Rc<Cell<usize>>) -> Entity {
+    let text = world.add(Some(parent));
+    world.set_width(text, Units::Auto);
+    world.set_height(text, Units::Auto);
+
+    let measured = measured.clone();
+    world.set_content_size(text, move |_, _, _| {
+        measured.set(measured.get() + 1);
+        (80.0, 20.0)
+    });
+
+    text
+}
+
+#[test]
+fn incremental_skips_unchanged_layout() {
+    let mut world = World::default();
+    let measured = Rc::new(Cell::new(0));
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let text = add_text(&mut world, root, &measured);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    let count = measured.get();
+    assert!(count > 0);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    assert_eq!(measured.get(), count);
+    assert_eq!(world.cache.bounds(text), Some(&Rect { posx: 0.0, posy: 0.0, width: 80.0, height: 20.0 }));
+}
+
+#[test]
+fn incremental_skips_clean_subtrees() {
+    let mut world = World::default();
+    let measured = Rc::new(Cell::new(0));
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    let panel1 = world.add(Some(root));
+    world.set_width(panel1, Units::Pixels(200.0));
+    world.set_height(panel1, Units::Pixels(200.0));
+    let text = add_text(&mut world, panel1, &measured);
+
+    let panel2 = world.add(Some(root));
+    world.set_width(panel2, Units::Pixels(200.0));
+    world.set_height(panel2, Units::Pixels(200.0));
+    let node = world.add(Some(panel2));
+    world.set_width(node, Units::Pixels(50.0));
+    world.set_height(node, Units::Pixels(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    let count = measured.get();
+
+    // Only the second panel and its ancestors are marked dirty, and the first panel is given the same space.
+    world.set_width(node, Units::Pixels(100.0));
+    world.set_left(node, Units::Pixels(10.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    assert_eq!(measured.get(), count);
+
+    assert_eq!(world.cache.bounds(text), Some(&Rect { posx: 0.0, posy: 0.0, width: 80.0, height: 20.0 }));
+    assert_eq!(world.cache.bounds(panel2), Some(&Rect { posx: 200.0, posy: 0.0, width: 200.0, height: 200.0 }));
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 10.0, posy: 0.0, width: 100.0, height: 50.0 }));
+
+    // Changing the space given to the first panel lays it out again, reusing the measured content of the text.
+    world.set_width(root, Units::Pixels(800.0));
+    world.set_width(panel1, Units::Stretch(1.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    assert_eq!(measured.get(), count);
+    assert_eq!(world.cache.bounds(panel1), Some(&Rect { posx: 0.0, posy: 0.0, width: 600.0, height: 200.0 }));
+}
+
+#[test]
+fn incremental_relayouts_dirty_nodes() {
+    let mut world = World::default();
+    let measured = Rc::new(Cell::new(0));
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let panel = world.add(Some(root));
+    world.set_width(panel, Units::Pixels(200.0));
+    world.set_height(panel, Units::Auto);
+    let text = add_text(&mut world, panel, &measured);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    let count = measured.get();
+
+    // The content of the text changes without any of its properties being set.
+    world.mark_dirty(text);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    assert!(measured.get() > count);
+    assert_eq!(world.cache.bounds(panel), Some(&Rect { posx: 0.0, posy: 0.0, width: 200.0, height: 20.0 }));
+}
+
+fn sticky_world(scroll: Scalar) -> (World, Vec<Entity>) {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_vertical_overflow(root, Overflow::Scroll);
+    world.set_vertical_scroll(root, scroll);
+    world.set_scale_factor(root, 1.5);
+    world.set_pixel_snapping(root, PixelSnapping::Edges);
+
+    // The panel is not changed when the root is scrolled, so its layout can be skipped.
+    let panel = world.add(Some(root));
+    world.set_width(panel, Units::Pixels(300.0));
+    world.set_height(panel, Units::Pixels(1000.0));
+    world.set_top(panel, Units::Pixels(10.3));
+
+    let header = world.add(Some(panel));
+    world.set_width(header, Units::Pixels(300.0));
+    world.set_height(header, Units::Pixels(40.4));
+    world.set_position_type(header, PositionType::Sticky);
+    world.set_top(header, Units::Pixels(5.3));
+
+    let item = world.add(Some(panel));
+    world.set_width(item, Units::Pixels(300.0));
+    world.set_height(item, Units::Pixels(500.0));
+
+    (world, vec![root, panel, header, item])
+}
+
+#[test]
+fn incremental_matches_full_layout() {
+    let (mut world, nodes) = sticky_world(-200.0);
+    nodes[0].layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The sticky header is restored to its position in the flow before it sticks to the new view.
+    world.set_vertical_scroll(nodes[0], -20.0);
+    nodes[0].layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    let (mut expected, _) = sticky_world(-20.0);
+    nodes[0].layout(&mut expected.cache, &expected.tree, &expected.store, &mut ());
+
+    for node in nodes {
+        assert_eq!(world.cache.bounds(node), expected.cache.bounds(node));
+    }
+}
+
+// Records the nodes which are laid out and the nodes whose results are written by the layout or the passes after it.
+struct VisitCache<'c> {
+    cache: &'c mut NodeCache,
+    laid_out: HashSet<Entity>,
+    touched: HashSet<Entity>,
+}
+
+impl<'c> VisitCache<'c> {
+    fn new(cache: &'c mut NodeCache) -> Self {
+        Self { cache, laid_out: HashSet::new(), touched: HashSet::new() }
+    }
+}
+
+impl Cache for VisitCache<'_> {
+    type Node = Entity;
+
+    fn width(&self, node: &Self::Node) -> Scalar {
+        self.cache.width(node)
+    }
+
+    fn height(&self, node: &Self::Node) -> Scalar {
+        self.cache.height(node)
+    }
+
+    fn posx(&self, node: &Self::Node) -> Scalar {
+        self.cache.posx(node)
+    }
+
+    fn posy(&self, node: &Self::Node) -> Scalar {
+        self.cache.posy(node)
+    }
+
+    fn set_bounds(&mut self, node: &Self::Node, posx: Scalar, posy: Scalar, width: Scalar, height: Scalar) {
+        self.touched.insert(*node);
+        self.cache.set_bounds(node, posx, posy, width, height);
+    }
+
+    fn set_clip(&mut self, node: &Self::Node, posx: Scalar, posy: Scalar, width: Scalar, height: Scalar) {
+        self.touched.insert(*node);
+        self.cache.set_clip(node, posx, posy, width, height);
+    }
+
+    fn set_stacking_order(&mut self, node: &Self::Node, index: usize) {
+        self.touched.insert(*node);
+        self.cache.set_stacking_order(node, index);
+    }
+
+    fn set_window_bounds(&mut self, node: &Self::Node, posx: Scalar, posy: Scalar, width: Scalar, height: Scalar) {
+        self.touched.insert(*node);
+        self.cache.set_window_bounds(node, posx, posy, width, height);
+    }
+
+    fn set_transform(&mut self, node: &Self::Node, transform: Transform) {
+        self.touched.insert(*node);
+        self.cache.set_transform(node, transform);
+    }
+
+    fn layout_record(&self, node: &Self::Node) -> Option<LayoutRecord> {
+        self.cache.layout_record(node)
+    }
+
+    fn set_layout_record(&mut self, node: &Self::Node, record: LayoutRecord) {
+        self.laid_out.insert(*node);
+        self.cache.set_layout_record(node, record);
+    }
+
+    fn post_layout_record(&self, node: &Self::Node) -> Option<PostLayoutRecord> {
+        self.cache.post_layout_record(node)
+    }
+
+    fn set_post_layout_record(&mut self, node: &Self::Node, record: PostLayoutRecord) {
+        self.touched.insert(*node);
+        self.cache.set_post_layout_record(node, record);
+    }
+}
+
+#[test]
+fn incremental_visits_only_dirty_path() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let mut leaves = Vec::new();
+    for _ in 0..10 {
+        let panel = world.add(Some(root));
+        world.set_width(panel, Units::Pixels(200.0));
+        world.set_height(panel, Units::Pixels(50.0));
+
+        let leaf = world.add(Some(panel));
+        world.set_width(leaf, Units::Pixels(50.0));
+        world.set_height(leaf, Units::Pixels(50.0));
+        leaves.push(leaf);
+    }
+
+    let mut cache = VisitCache::new(&mut world.cache);
+    root.layout(&mut cache, &world.tree, &world.store, &mut ());
+    assert_eq!(cache.laid_out.len(), 21);
+    assert_eq!(cache.touched.len(), 21);
+
+    let leaf = leaves[3];
+    let panel = *world.tree.get_parent(&leaf).unwrap();
+    world.set_width(leaf, Units::Pixels(100.0));
+
+    // Only the changed leaf and its ancestors are measured and arranged. The other panels are placed by the root, but
+    // the passes after layout leave their leaves untouched.
+    let mut cache = VisitCache::new(&mut world.cache);
+    root.layout(&mut cache, &world.tree, &world.store, &mut ());
+    assert_eq!(cache.laid_out, HashSet::from([root, panel, leaf]));
+
+    let panels = world
+        .tree
+        .get_first_child(&root)
+        .copied()
+        .into_iter()
+        .chain(leaves.iter().map(|leaf| *world.tree.get_parent(leaf).unwrap()));
+    let expected = panels.chain([root, leaf]).collect::<HashSet<_>>();
+    assert_eq!(cache.touched, expected);
+
+    // Nothing is written when nothing has changed.
+    let mut cache = VisitCache::new(&mut world.cache);
+    root.layout(&mut cache, &world.tree, &world.store, &mut ());
+    assert!(cache.laid_out.is_empty());
+    assert_eq!(cache.touched, HashSet::from([root]));
+}
+
+// Builds a tree which uses every pass after layout, for comparing incremental layout with a full layout.
+fn post_layout_world() -> (World, Vec<Entity>) {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_vertical_overflow(root, Overflow::Scroll);
+    world.set_scale_factor(root, 1.5);
+    world.set_pixel_snapping(root, PixelSnapping::Edges);
+
+    let mut nodes = vec![root];
+    for index in 0..6 {
+        let panel = world.add(Some(root));
+        world.set_width(panel, Units::Pixels(300.3));
+        world.set_height(panel, Units::Pixels(250.7));
+        world.set_vertical_overflow(panel, Overflow::Hidden);
+
+        let header = world.add(Some(panel));
+        world.set_height(header, Units::Pixels(40.4));
+        world.set_position_type(header, PositionType::Sticky);
+        world.set_top(header, Units::Pixels(5.3));
+
+        let item = world.add(Some(panel));
+        world.set_width(item, Units::Pixels(120.5));
+        world.set_height(item, Units::Pixels(300.2));
+        world.set_transform(item, Transform::rotate(0.1 * index as Scalar));
+
+        let leaf = world.add(Some(item));
+        world.set_width(leaf, Units::Pixels(30.1));
+        world.set_height(leaf, Units::Pixels(30.1));
+        if index % 3 == 0 {
+            world.set_z_index(leaf, 1);
+        }
+
+        nodes.extend([panel, header, item, leaf]);
+    }
+
+    // A fixed node within the first panel and a popover within the last panel anchored to the second panel.
+    let fixed = world.add(Some(nodes[1]));
+    world.set_position_type(fixed, PositionType::Fixed);
+    world.set_width(fixed, Units::Pixels(50.0));
+    world.set_height(fixed, Units::Pixels(50.0));
+    world.set_right(fixed, Units::Pixels(10.0));
+    world.set_bottom(fixed, Units::Pixels(10.0));
+
+    let popover = world.add(Some(nodes[21]));
+    world.set_position_type(popover, PositionType::Absolute);
+    world.set_width(popover, Units::Pixels(80.0));
+    world.set_height(popover, Units::Pixels(20.0));
+    world.set_anchor(popover, nodes[5]);
+    world.set_placement(popover, Placement::BelowStart);
+
+    nodes.extend([fixed, popover]);
+    (world, nodes)
+}
+
+#[test]
+fn incremental_post_layout_matches_full_layout() {
+    let changes: Vec<fn(&mut World, &[Entity])> = vec![
+        |world, nodes| world.set_vertical_scroll(nodes[0], -120.0),
+        |world, nodes| world.set_width(nodes[8], Units::Pixels(60.2)),
+        |world, nodes| world.set_height(nodes[5], Units::Pixels(100.0)),
+        |world, nodes| world.set_visibility(nodes[1], false),
+        |world, nodes| world.set_scale_factor(nodes[0], 2.0),
+        |world, nodes| world.set_visibility(nodes[1], true),
+        |world, nodes| world.set_z_index(nodes[13], -1),
+        |world, nodes| world.set_transform(nodes[0], Transform::rotate(0.2)),
+        |world, nodes| world.set_vertical_scroll(nodes[0], 0.0),
+        |world, nodes| world.set_transform(nodes[7], Transform::scale(0.5, 0.5)),
+        |world, nodes| world.set_anchor(nodes[26], nodes[8]),
+        |world, nodes| world.set_width(nodes[12], Units::Pixels(40.0)),
+        |world, nodes| world.set_width(nodes[0], Units::Pixels(500.0)),
+    ];
+
+    let (mut world, nodes) = post_layout_world();
+    nodes[0].layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    for (index, change) in changes.iter().enumerate() {
+        change(&mut world, &nodes);
+        nodes[0].layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+        let (mut expected, _) = post_layout_world();
+        for change in &changes[..=index] {
+            change(&mut expected, &nodes);
+        }
+        nodes[0].layout(&mut expected.cache, &expected.tree, &expected.store, &mut ());
+
+        // Hidden nodes keep the results of their last layout, so only the visible nodes are compared.
+        assert_eq!(world.cache.paint_order(), expected.cache.paint_order(), "paint order after {index}");
+
+        for node in &expected.cache.paint_order() {
+            assert_eq!(world.cache.bounds(*node), expected.cache.bounds(*node), "bounds of {node} after {index}");
+            assert_eq!(world.cache.clip(*node), expected.cache.clip(*node), "clip of {node} after {index}");
+            assert_eq!(world.cache.window_bounds(*node), expected.cache.window_bounds(*node), "{node} after {index}");
+            assert_eq!(world.cache.transform(*node), expected.cache.transform(*node), "{node} after {index}");
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+// Simulates a text label made of three 40px words on 20px lines.
+fn text(_: &Store, width: Option<Scalar>, _: Option<Scalar>) -> (Scalar, Scalar) {
+    let width = width.unwrap_or(120.0).clamp(40.0, 120.0);
+    let words_per_line = (width / 40.0).floor();
+    let lines = (3.0 / words_per_line).ceil();
+    (words_per_line.min(3.0) * 40.0, lines * 20.0)
+}
+
+#[test]
+fn min_content_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::MinContent);
+    world.set_height(node, Units::Auto);
+    world.set_content_size(node, text);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 40.0, height: 60.0 }));
+}
+
+#[test]
+fn max_content_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::MaxContent);
+    world.set_height(node, Units::Auto);
+    world.set_content_size(node, text);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 120.0, height: 20.0 }));
+}
+
+#[test]
+fn fit_content_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(90.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::FitContent);
+    world.set_height(node, Units::Auto);
+    world.set_content_size(node, text);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 80.0, height: 40.0 }));
+
+    world.set_width(root, Units::Pixels(600.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 120.0, height: 20.0 }));
+
+    world.set_width(root, Units::Pixels(20.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 40.0, height: 60.0 }));
+}
+
+#[test]
+fn max_content_row_container() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let container = world.add(Some(root));
+    world.set_layout_type(container, LayoutType::Row);
+    world.set_width(container, Units::MaxContent);
+    world.set_height(container, Units::Auto);
+    world.set_padding(container, Units::Pixels(10.0));
+    world.set_horizontal_gap(container, Units::Pixels(5.0));
+
+    let node1 = world.add(Some(container));
+    world.set_width(node1, Units::Auto);
+    world.set_height(node1, Units::Auto);
+    world.set_content_size(node1, text);
+
+    let node2 = world.add(Some(container));
+    world.set_width(node2, Units::Pixels(50.0));
+    world.set_height(node2, Units::Pixels(20.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(container), Some(&Rect { posx: 0.0, posy: 0.0, width: 195.0, height: 40.0 }));
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 10.0, posy: 10.0, width: 120.0, height: 20.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 135.0, posy: 10.0, width: 50.0, height: 20.0 }));
+}
+
+#[test]
+fn min_content_column_container() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let container = world.add(Some(root));
+    world.set_width(container, Units::MinContent);
+    world.set_height(container, Units::Auto);
+
+    let node1 = world.add(Some(container));
+    world.set_width(node1, Units::Stretch(1.0));
+    world.set_height(node1, Units::Auto);
+    world.set_content_size(node1, text);
+
+    let node2 = world.add(Some(container));
+    world.set_width(node2, Units::Pixels(30.0));
+    world.set_height(node2, Units::Pixels(20.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(container), Some(&Rect { posx: 0.0, posy: 0.0, width: 40.0, height: 80.0 }));
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: 0.0, width: 40.0, height: 60.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 0.0, posy: 60.0, width: 30.0, height: 20.0 }));
+}
+
+#[test]
+fn min_content_wrap_container() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let container = world.add(Some(root));
+    world.set_layout_type(container, LayoutType::Row);
+    world.set_wrap(container, LayoutWrap::Wrap);
+    world.set_width(container, Units::MinContent);
+    world.set_height(container, Units::Auto);
+
+    let node1 = world.add(Some(container));
+    world.set_width(node1, Units::Pixels(50.0));
+    world.set_height(node1, Units::Pixels(20.0));
+
+    let node2 = world.add(Some(container));
+    world.set_width(node2, Units::Pixels(70.0));
+    world.set_height(node2, Units::Pixels(20.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(container), Some(&Rect { posx: 0.0, posy: 0.0, width: 70.0, height: 40.0 }));
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: 0.0, width: 50.0, height: 20.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 0.0, posy: 20.0, width: 70.0, height: 20.0 }));
+}
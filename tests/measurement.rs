@@ -0,0 +1,157 @@
+use std::{cell::Cell, rc::Rc};
+
+use morphorm::*;
+use morphorm_ecs::*;
+
+// Adds a text node whose content size is counted each time it is measured.
+fn add_text(world: &mut World, parent: Entity, measured: &Rc<Cell<usize>>) -> Entity {
+    let text = world.add(Some(parent));
+    world.set_width(text, Units::Auto);
+    world.set_height(text, Units::Auto);
+
+    let measured = measured.clone();
+    world.set_content_size(text, move |_, _, _| {
+        measured.set(measured.get() + 1);
+        (80.0, 20.0)
+    });
+
+    text
+}
+
+#[test]
+fn content_measured_once_per_constraints() {
+    let mut world = World::default();
+    let measured = Rc::new(Cell::new(0));
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    // The content minimum and the auto size both measure the content without constraints.
+    let text = add_text(&mut world, root, &measured);
+    world.set_min_width(text, Units::Auto);
+    world.set_min_height(text, Units::Auto);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(measured.get(), 1);
+    assert_eq!(world.cache.bounds(text), Some(&Rect { posx: 0.0, posy: 0.0, width: 80.0, height: 20.0 }));
+}
+
+#[test]
+fn content_measured_for_each_constraints() {
+    let mut world = World::default();
+    let constraints = Rc::new(Cell::new(Vec::new()));
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    // The minimum width is measured with no space, while the height is measured with the computed width.
+    let text = world.add(Some(root));
+    world.set_width(text, Units::Pixels(100.0));
+    world.set_height(text, Units::Auto);
+    world.set_min_width(text, Units::MinContent);
+
+    let recorded = constraints.clone();
+    world.set_content_size(text, move |_, width, height| {
+        let mut list = recorded.take();
+        list.push((width, height));
+        recorded.set(list);
+        (width.unwrap_or(80.0), 20.0)
+    });
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(constraints.take(), vec![(Some(0.0), None), (Some(100.0), None)]);
+    assert_eq!(world.cache.bounds(text), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 20.0 }));
+}
+
+#[test]
+fn content_measurements_reused_across_layouts() {
+    let mut world = World::default();
+    let measured = Rc::new(Cell::new(0));
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let text = add_text(&mut world, root, &measured);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    assert_eq!(measured.get(), 1);
+
+    // The text is laid out again in the resized root, but its content is measured with the same constraints.
+    world.set_width(root, Units::Pixels(400.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    assert_eq!(measured.get(), 1);
+    assert_eq!(world.cache.bounds(text), Some(&Rect { posx: 0.0, posy: 0.0, width: 80.0, height: 20.0 }));
+}
+
+#[test]
+fn content_measurements_discarded_when_dirty() {
+    let mut world = World::default();
+    let measured = Rc::new(Cell::new(0));
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let text = add_text(&mut world, root, &measured);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    assert_eq!(measured.get(), 1);
+
+    // The content of the text changes without any of its layout properties.
+    world.mark_dirty(text);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    assert_eq!(measured.get(), 2);
+    assert_eq!(world.cache.bounds(text), Some(&Rect { posx: 0.0, posy: 0.0, width: 80.0, height: 20.0 }));
+}
+
+#[test]
+fn content_measurements_keep_latest_constraints() {
+    let mut world = World::default();
+    let measured = Rc::new(Cell::new(0));
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    // The text is measured with the width of the root, which changes as the root is resized.
+    let text = add_text(&mut world, root, &measured);
+    world.set_width(text, Units::Stretch(1.0));
+
+    for width in [600.0, 500.0, 400.0] {
+        world.set_width(root, Units::Pixels(width));
+        root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    }
+
+    assert_eq!(measured.get(), 3);
+    assert_eq!(world.cache.measurements.get(text).map(|measurements| measurements.len()), Some(2));
+
+    // Measurements with the latest widths are reused, while the oldest has been replaced.
+    world.set_width(root, Units::Pixels(500.0));
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    assert_eq!(measured.get(), 3);
+
+    world.set_width(root, Units::Pixels(600.0));
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+    assert_eq!(measured.get(), 4);
+}
+
+#[test]
+fn content_measurements_compare_constraint_bits() {
+    let mut world = World::default();
+
+    let node = world.add(None);
+    world.cache.set_content_measurement(&node, Some(0.0), Some(Scalar::NAN), (10.0, 20.0));
+
+    assert_eq!(world.cache.content_measurement(&node, Some(0.0), Some(Scalar::NAN)), Some((10.0, 20.0)));
+    assert_eq!(world.cache.content_measurement(&node, Some(-0.0), Some(Scalar::NAN)), None);
+    assert_eq!(world.cache.content_measurement(&node, Some(0.0), None), None);
+}
@@ -0,0 +1,134 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+fn add_button(world: &mut World, parent: Entity, left: Scalar, top: Scalar) -> Entity {
+    let button = world.add(Some(parent));
+    world.set_width(button, Units::Pixels(100.0));
+    world.set_height(button, Units::Pixels(100.0));
+    world.set_left(button, Units::Pixels(left));
+    world.set_top(button, Units::Pixels(top));
+    world.set_position_type(button, PositionType::Absolute);
+    world.set_focusable(button, true);
+    button
+}
+
+#[test]
+fn navigate_grid() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let mut grid = Vec::new();
+    for row in 0..3 {
+        for col in 0..3 {
+            grid.push(add_button(&mut world, root, col as Scalar * 150.0, row as Scalar * 150.0));
+        }
+    }
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    let center = &grid[4];
+    assert_eq!(navigate(&root, center, FocusDirection::Right, &world.cache, &world.tree, &world.store), Some(&grid[5]));
+    assert_eq!(navigate(&root, center, FocusDirection::Down, &world.cache, &world.tree, &world.store), Some(&grid[7]));
+    assert_eq!(navigate(&root, center, FocusDirection::Left, &world.cache, &world.tree, &world.store), Some(&grid[3]));
+    assert_eq!(navigate(&root, center, FocusDirection::Up, &world.cache, &world.tree, &world.store), Some(&grid[1]));
+
+    // Nodes which overlap on the perpendicular axis are preferred over nearer diagonal nodes.
+    let corner = &grid[0];
+    assert_eq!(navigate(&root, corner, FocusDirection::Right, &world.cache, &world.tree, &world.store), Some(&grid[1]));
+    assert_eq!(navigate(&root, &grid[6], FocusDirection::Up, &world.cache, &world.tree, &world.store), Some(&grid[3]));
+}
+
+#[test]
+fn navigate_returns_none_at_edge() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = add_button(&mut world, root, 0.0, 0.0);
+    let node2 = add_button(&mut world, root, 150.0, 0.0);
+
+    // Nodes which are not focusable are skipped.
+    let node3 = add_button(&mut world, root, 300.0, 0.0);
+    world.set_focusable(node3, false);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(navigate(&root, &node1, FocusDirection::Right, &world.cache, &world.tree, &world.store), Some(&node2));
+    assert_eq!(navigate(&root, &node2, FocusDirection::Right, &world.cache, &world.tree, &world.store), None);
+    assert_eq!(navigate(&root, &node1, FocusDirection::Up, &world.cache, &world.tree, &world.store), None);
+    assert_eq!(navigate(&root, &node1, FocusDirection::Down, &world.cache, &world.tree, &world.store), None);
+}
+
+fn add_list(world: &mut World, parent: Entity) -> (Entity, Vec<Entity>) {
+    let list = world.add(Some(parent));
+    world.set_width(list, Units::Pixels(200.0));
+    world.set_height(list, Units::Pixels(300.0));
+    world.set_vertical_overflow(list, Overflow::Scroll);
+
+    let items = (0..5)
+        .map(|_| {
+            let item = world.add(Some(list));
+            world.set_width(item, Units::Pixels(200.0));
+            world.set_height(item, Units::Pixels(100.0));
+            world.set_focusable(item, true);
+            item
+        })
+        .collect();
+
+    (list, items)
+}
+
+#[test]
+fn navigate_prefers_same_scroll_container() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(800.0));
+
+    let (_, items) = add_list(&mut world, root);
+    let button = add_button(&mut world, root, 0.0, 550.0);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The next item is scrolled out of view but is preferred over the button below the list.
+    assert_eq!(
+        navigate(&root, &items[2], FocusDirection::Down, &world.cache, &world.tree, &world.store),
+        Some(&items[3])
+    );
+
+    // Focus leaves the list after the last item.
+    assert_eq!(
+        navigate(&root, &items[4], FocusDirection::Down, &world.cache, &world.tree, &world.store),
+        Some(&button)
+    );
+    assert_eq!(navigate(&root, &button, FocusDirection::Up, &world.cache, &world.tree, &world.store), Some(&items[2]));
+}
+
+#[test]
+fn navigate_skips_items_out_of_view() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let (list, items) = add_list(&mut world, root);
+    world.set_top(list, Units::Pixels(200.0));
+    world.set_vertical_scroll(list, -200.0);
+
+    let button = add_button(&mut world, root, 400.0, 0.0);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The first two items are level with the button but scrolled out of view above the list.
+    assert_eq!(
+        navigate(&root, &button, FocusDirection::Left, &world.cache, &world.tree, &world.store),
+        Some(&items[2])
+    );
+}
@@ -0,0 +1,155 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn visible_overflow_clip_is_bounds() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(800.0));
+    world.set_height(node, Units::Pixels(150.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.clip(root), Some(&Rect { posx: 0.0, posy: 0.0, width: 600.0, height: 600.0 }));
+    assert_eq!(world.cache.clip(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 800.0, height: 150.0 }));
+}
+
+#[test]
+fn hidden_overflow_clips_children() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_overflow(root, Overflow::Hidden);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(800.0));
+    world.set_height(node, Units::Pixels(800.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.clip(root), Some(&Rect { posx: 0.0, posy: 0.0, width: 600.0, height: 600.0 }));
+    assert_eq!(world.cache.clip(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 600.0, height: 600.0 }));
+}
+
+#[test]
+fn hidden_overflow_single_axis() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_vertical_overflow(root, Overflow::Scroll);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(800.0));
+    world.set_height(node, Units::Pixels(800.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.clip(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 800.0, height: 600.0 }));
+}
+
+#[test]
+fn nested_clips_intersect() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_overflow(root, Overflow::Hidden);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(300.0));
+    world.set_height(node, Units::Pixels(300.0));
+    world.set_position_type(node, PositionType::Absolute);
+    world.set_left(node, Units::Pixels(400.0));
+    world.set_top(node, Units::Pixels(100.0));
+    world.set_border(node, Units::Pixels(10.0));
+    world.set_overflow(node, Overflow::Hidden);
+
+    let child = world.add(Some(node));
+    world.set_width(child, Units::Pixels(500.0));
+    world.set_height(child, Units::Pixels(100.0));
+    world.set_position_type(child, PositionType::Absolute);
+    world.set_left(child, Units::Pixels(-50.0));
+    world.set_top(child, Units::Pixels(250.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.clip(node), Some(&Rect { posx: 400.0, posy: 100.0, width: 200.0, height: 300.0 }));
+    // The child is clipped to the padding box of its parent and the bounds of the root, in the space of its parent.
+    assert_eq!(world.cache.clip(child), Some(&Rect { posx: 10.0, posy: 260.0, width: 190.0, height: 30.0 }));
+}
+
+#[test]
+fn hidden_overflow_ignores_content_min_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(100.0));
+    world.set_height(node, Units::Pixels(100.0));
+    world.set_min_height(node, Units::Auto);
+
+    let child = world.add(Some(node));
+    world.set_width(child, Units::Pixels(100.0));
+    world.set_height(child, Units::Pixels(300.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 300.0 }));
+
+    world.set_vertical_overflow(node, Overflow::Hidden);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.clip(child), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 100.0 }));
+}
+
+#[test]
+fn hidden_overflow_uses_layout_borders() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    // The percentage borders on the auto width resolve against the size before the children are laid out.
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Auto);
+    world.set_height(node, Units::Pixels(100.0));
+    world.set_border(node, Units::Percentage(10.0));
+    world.set_overflow(node, Overflow::Hidden);
+
+    let child = world.add(Some(node));
+    world.set_width(child, Units::Pixels(100.0));
+    world.set_height(child, Units::Pixels(50.0));
+
+    let sibling = world.add(Some(root));
+    world.set_width(sibling, Units::Pixels(100.0));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(child), Some(&Rect { posx: 0.0, posy: 10.0, width: 100.0, height: 50.0 }));
+    assert_eq!(world.cache.clip(child), Some(&Rect { posx: 0.0, posy: 10.0, width: 100.0, height: 50.0 }));
+
+    // The borders are kept when the layout of the node is skipped.
+    world.set_height(sibling, Units::Pixels(200.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.clip(child), Some(&Rect { posx: 0.0, posy: 10.0, width: 100.0, height: 50.0 }));
+}
@@ -118,13 +118,13 @@ fn pixels_padding_right_pixels_size() {
 
     root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
 
-    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 150.0 }));
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 480.0, posy: 0.0, width: 100.0, height: 150.0 }));
 
     world.set_layout_type(root, LayoutType::Row);
 
     root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
 
-    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 150.0 }));
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 480.0, posy: 0.0, width: 100.0, height: 150.0 }));
 }
 
 #[test]
@@ -144,13 +144,13 @@ fn percentage_padding_right_pixels_size() {
 
     root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
 
-    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 150.0 }));
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 200.0, posy: 0.0, width: 100.0, height: 150.0 }));
 
     world.set_layout_type(root, LayoutType::Row);
 
     root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
 
-    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 150.0 }));
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 200.0, posy: 0.0, width: 100.0, height: 150.0 }));
 }
 
 #[test]
@@ -170,13 +170,13 @@ fn pixels_padding_bottom_pixels_size() {
 
     root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
 
-    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 150.0 }));
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 430.0, width: 100.0, height: 150.0 }));
 
     world.set_layout_type(root, LayoutType::Row);
 
     root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
 
-    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 150.0 }));
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 430.0, width: 100.0, height: 150.0 }));
 }
 
 #[test]
@@ -0,0 +1,45 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn scalar_preserves_fractional_position() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(1000.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(100.0));
+    world.set_height(node, Units::Pixels(100.0));
+    world.set_left(node, Units::Pixels(0.25));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.25, posy: 0.0, width: 100.0, height: 100.0 }));
+}
+
+#[cfg(feature = "f64")]
+#[test]
+fn scalar_f64_precision_in_large_layout() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100_000_000.0));
+    world.set_height(root, Units::Pixels(1000.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(50_000_000.0));
+    world.set_height(node1, Units::Pixels(100.0));
+
+    // With `f32` the position of this node would be rounded to a multiple of 4 pixels.
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(0.5));
+    world.set_height(node2, Units::Pixels(100.0));
+    world.set_left(node2, Units::Pixels(0.25));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 50_000_000.25, posy: 0.0, width: 0.5, height: 100.0 }));
+}
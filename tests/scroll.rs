@@ -0,0 +1,197 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn scroll_offsets_aligned_position() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_alignment(root, Alignment::Center);
+    world.set_horizontal_scroll(root, -50.0);
+    world.set_vertical_scroll(root, 20.0);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(100.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(100.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 200.0, posy: 220.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 200.0, posy: 320.0, width: 100.0, height: 100.0 }));
+
+    world.set_layout_type(root, LayoutType::Row);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 150.0, posy: 270.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 250.0, posy: 270.0, width: 100.0, height: 100.0 }));
+}
+
+#[test]
+fn scroll_does_not_move_absolute_children() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_vertical_scroll(root, -100.0);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(100.0));
+    world.set_height(node, Units::Pixels(100.0));
+    world.set_position_type(node, PositionType::Absolute);
+    world.set_top(node, Units::Pixels(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 50.0, width: 100.0, height: 100.0 }));
+}
+
+#[test]
+fn scroll_overlay() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Overlay);
+    world.set_alignment(root, Alignment::BottomRight);
+    world.set_horizontal_scroll(root, -50.0);
+    world.set_vertical_scroll(root, -30.0);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(100.0));
+    world.set_height(node, Units::Pixels(100.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 450.0, posy: 470.0, width: 100.0, height: 100.0 }));
+}
+
+#[test]
+fn scroll_wrap() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(300.0));
+    world.set_height(root, Units::Pixels(300.0));
+    world.set_layout_type(root, LayoutType::Row);
+    world.set_wrap(root, LayoutWrap::Wrap);
+    world.set_vertical_scroll(root, -40.0);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(200.0));
+    world.set_height(node1, Units::Pixels(50.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(200.0));
+    world.set_height(node2, Units::Pixels(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: -40.0, width: 200.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 0.0, posy: 10.0, width: 200.0, height: 50.0 }));
+}
+
+#[test]
+fn scroll_grid() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Grid);
+    world.set_grid_columns(root, vec![Units::Pixels(100.0), Units::Pixels(100.0)]);
+    world.set_grid_rows(root, vec![Units::Pixels(100.0)]);
+    world.set_horizontal_scroll(root, -25.0);
+
+    let node = world.add(Some(root));
+    world.set_column_start(node, 1);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 75.0, posy: 0.0, width: 100.0, height: 100.0 }));
+}
+
+#[test]
+fn clamp_scroll_to_content() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_vertical_scroll(root, -1000.0);
+    world.set_horizontal_scroll(root, -10.0);
+    world.set_clamp_scroll(root, true);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(500.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(500.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The content overflows by 400px vertically and fits horizontally.
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: -400.0, width: 100.0, height: 500.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 0.0, posy: 100.0, width: 100.0, height: 500.0 }));
+
+    world.set_vertical_scroll(root, 50.0);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 500.0 }));
+}
+
+#[test]
+fn content_size_overflow() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_padding(root, Units::Pixels(10.0));
+    world.set_border(root, Units::Pixels(5.0));
+    world.set_vertical_scroll(root, -100.0);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(500.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(500.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The content is measured before scrolling and includes padding but not border.
+    assert_eq!(world.cache.content_size(root), Some((590.0, 1020.0)));
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 15.0, posy: -85.0, width: 100.0, height: 500.0 }));
+}
+
+#[test]
+fn content_size_no_overflow() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(100.0));
+    world.set_height(node, Units::Pixels(100.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.content_size(root), Some((600.0, 600.0)));
+    assert_eq!(world.cache.content_size(node), Some((100.0, 100.0)));
+}
@@ -0,0 +1,91 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn height_relative_to_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(200.0));
+    world.set_height(node, Units::SelfWidth(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 200.0, height: 100.0 }));
+
+    world.set_layout_type(root, LayoutType::Column);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 200.0, height: 100.0 }));
+}
+
+#[test]
+fn width_relative_to_stretch_height() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(200.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::SelfHeight(150.0));
+    world.set_height(node, Units::Stretch(1.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Stretch(1.0));
+    world.set_height(node2, Units::Stretch(1.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 300.0, height: 200.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 300.0, posy: 0.0, width: 300.0, height: 200.0 }));
+}
+
+#[test]
+fn size_relative_to_content_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Auto);
+    world.set_height(node, Units::SelfMin(25.0));
+    world.set_content_size(node, |_, _, _| (160.0, 20.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 160.0, height: 40.0 }));
+}
+
+#[test]
+fn padding_relative_to_smaller_side() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(200.0));
+    world.set_height(node, Units::Pixels(100.0));
+    world.set_padding(node, Units::SelfMin(10.0));
+    world.set_border(node, Units::SelfMax(1.0));
+
+    let child = world.add(Some(node));
+    world.set_width(child, Units::Stretch(1.0));
+    world.set_height(child, Units::Stretch(1.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 200.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(child), Some(&Rect { posx: 12.0, posy: 12.0, width: 176.0, height: 76.0 }));
+}
@@ -0,0 +1,95 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+fn assert_on_grid(value: Scalar, scale_factor: Scalar) {
+    let device = value * scale_factor;
+    assert!((device - device.round()).abs() < 1e-3, "{value} is not on the device pixel grid");
+}
+
+#[test]
+fn snapping_disabled_by_default() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(33.4));
+    world.set_height(node, Units::Pixels(20.3));
+    world.set_left(node, Units::Pixels(10.2));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 10.2, posy: 0.0, width: 33.4, height: 20.3 }));
+}
+
+#[test]
+fn snapping_rounds_edges() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_layout_type(root, LayoutType::Row);
+    world.set_pixel_snapping(root, PixelSnapping::Edges);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(10.4));
+    world.set_height(node, Units::Pixels(10.4));
+    world.set_left(node, Units::Pixels(10.4));
+    world.set_top(node, Units::Pixels(10.4));
+    world.set_layout_type(node, LayoutType::Row);
+
+    // The child starts at 10.8 and ends at 16.0 relative to the root.
+    let child = world.add(Some(node));
+    world.set_width(child, Units::Pixels(5.2));
+    world.set_height(child, Units::Pixels(5.2));
+    world.set_left(child, Units::Pixels(0.4));
+    world.set_top(child, Units::Pixels(0.4));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The right edge at 20.8 is rounded up, so the size grows even though 10.4 rounds down.
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 10.0, posy: 10.0, width: 11.0, height: 11.0 }));
+
+    // The child is positioned relative to the rounded position of its parent.
+    assert_eq!(world.cache.bounds(child), Some(&Rect { posx: 1.0, posy: 1.0, width: 5.0, height: 5.0 }));
+}
+
+#[test]
+fn snapping_keeps_siblings_adjacent_at_fractional_scale() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_layout_type(root, LayoutType::Row);
+    world.set_scale_factor(root, 1.5);
+    world.set_pixel_snapping(root, PixelSnapping::Edges);
+
+    let nodes: Vec<_> = (0..3)
+        .map(|_| {
+            let node = world.add(Some(root));
+            world.set_width(node, Units::Pixels(33.4));
+            world.set_height(node, Units::Pixels(20.3));
+            node
+        })
+        .collect();
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    let mut right = 0.0;
+    for node in nodes {
+        let bounds = world.cache.bounds(node).unwrap();
+        assert!((bounds.posx - right).abs() < 1e-4);
+        assert_on_grid(bounds.posx, 1.5);
+        assert_on_grid(bounds.width, 1.5);
+        assert_on_grid(bounds.height, 1.5);
+        right = bounds.posx + bounds.width;
+    }
+
+    // The last edge at 100.2 rounds to 150 device pixels.
+    assert!((right - 100.0).abs() < 1e-4);
+}
@@ -0,0 +1,95 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn stacking_order_follows_tree_order() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    let node2 = world.add(Some(node1));
+    let node3 = world.add(Some(root));
+
+    let node4 = world.add(Some(root));
+    world.set_visibility(node4, false);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.paint_order(), &[root, node1, node2, node3]);
+}
+
+#[test]
+fn z_index_paints_popover_above_later_content() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let header = world.add(Some(root));
+
+    let popover = world.add(Some(header));
+    world.set_position_type(popover, PositionType::Absolute);
+    world.set_z_index(popover, 1);
+
+    let item = world.add(Some(popover));
+
+    let content = world.add(Some(root));
+    let child = world.add(Some(content));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.paint_order(), &[root, header, content, child, popover, item]);
+}
+
+#[test]
+fn negative_z_index_paints_below_content() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+
+    let node2 = world.add(Some(root));
+    world.set_z_index(node2, -1);
+
+    let node3 = world.add(Some(root));
+    world.set_z_index(node3, -2);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.paint_order(), &[root, node3, node2, node1]);
+}
+
+#[test]
+fn z_index_is_local_to_stacking_context() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_z_index(node1, 2);
+
+    let node2 = world.add(Some(node1));
+    world.set_z_index(node2, 100);
+
+    let node3 = world.add(Some(node1));
+
+    let node4 = world.add(Some(root));
+    world.set_z_index(node4, 2);
+
+    let node5 = world.add(Some(root));
+    world.set_z_index(node5, 1);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // Equal z-indices are painted in tree order, and a z-index only orders a node within its stacking context.
+    assert_eq!(world.cache.paint_order(), &[root, node5, node1, node3, node2, node4]);
+}
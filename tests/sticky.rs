@@ -0,0 +1,155 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn sticky_without_insets_is_relative() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_vertical_scroll(root, -50.0);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(100.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(100.0));
+    world.set_position_type(node2, PositionType::Sticky);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: -50.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 0.0, posy: 50.0, width: 100.0, height: 100.0 }));
+}
+
+#[test]
+fn sticky_insets_are_not_margins() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_vertical_overflow(root, Overflow::Scroll);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(100.0));
+    world.set_position_type(node1, PositionType::Sticky);
+    world.set_top(node1, Units::Pixels(30.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(100.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The top inset holds the sticky node away from the top of the view without taking up space in the flow.
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: 30.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 0.0, posy: 100.0, width: 100.0, height: 100.0 }));
+}
+
+#[test]
+fn sticky_top_pins_to_scroll_container() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(400.0));
+    world.set_height(root, Units::Pixels(400.0));
+    world.set_vertical_overflow(root, Overflow::Scroll);
+    world.set_vertical_scroll(root, -150.0);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(100.0));
+
+    let header = world.add(Some(root));
+    world.set_width(header, Units::Stretch(1.0));
+    world.set_height(header, Units::Pixels(50.0));
+    world.set_position_type(header, PositionType::Sticky);
+    world.set_top(header, Units::Pixels(10.0));
+
+    let node3 = world.add(Some(root));
+    world.set_width(node3, Units::Pixels(100.0));
+    world.set_height(node3, Units::Pixels(1000.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: -150.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(header), Some(&Rect { posx: 0.0, posy: 10.0, width: 400.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(node3), Some(&Rect { posx: 0.0, posy: -0.0, width: 100.0, height: 1000.0 }));
+
+    world.set_vertical_scroll(root, -20.0);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The header has not yet reached its threshold.
+    assert_eq!(world.cache.bounds(header), Some(&Rect { posx: 0.0, posy: 80.0, width: 400.0, height: 50.0 }));
+}
+
+#[test]
+fn sticky_bottom_pins_to_scroll_container() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(400.0));
+    world.set_height(root, Units::Pixels(400.0));
+    world.set_vertical_overflow(root, Overflow::Scroll);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(500.0));
+
+    let footer = world.add(Some(root));
+    world.set_width(footer, Units::Pixels(100.0));
+    world.set_height(footer, Units::Pixels(50.0));
+    world.set_position_type(footer, PositionType::Sticky);
+    world.set_bottom(footer, Units::Pixels(0.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(footer), Some(&Rect { posx: 0.0, posy: 350.0, width: 100.0, height: 50.0 }));
+}
+
+#[test]
+fn sticky_pushed_out_by_parent() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(400.0));
+    world.set_height(root, Units::Pixels(400.0));
+    world.set_vertical_overflow(root, Overflow::Scroll);
+    world.set_vertical_scroll(root, -300.0);
+
+    let section1 = world.add(Some(root));
+    world.set_width(section1, Units::Stretch(1.0));
+    world.set_height(section1, Units::Pixels(200.0));
+
+    let header1 = world.add(Some(section1));
+    world.set_width(header1, Units::Stretch(1.0));
+    world.set_height(header1, Units::Pixels(50.0));
+    world.set_position_type(header1, PositionType::Sticky);
+    world.set_top(header1, Units::Pixels(0.0));
+
+    let section2 = world.add(Some(root));
+    world.set_width(section2, Units::Stretch(1.0));
+    world.set_height(section2, Units::Pixels(600.0));
+
+    let header2 = world.add(Some(section2));
+    world.set_width(header2, Units::Stretch(1.0));
+    world.set_height(header2, Units::Pixels(50.0));
+    world.set_position_type(header2, PositionType::Sticky);
+    world.set_top(header2, Units::Pixels(0.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The first header is held at the end of its section, which has scrolled out of view.
+    assert_eq!(world.cache.bounds(section1), Some(&Rect { posx: 0.0, posy: -300.0, width: 400.0, height: 200.0 }));
+    assert_eq!(world.cache.bounds(header1), Some(&Rect { posx: 0.0, posy: 150.0, width: 400.0, height: 50.0 }));
+
+    // The second header is pinned to the top of the scroll container.
+    assert_eq!(world.cache.bounds(section2), Some(&Rect { posx: 0.0, posy: -100.0, width: 400.0, height: 600.0 }));
+    assert_eq!(world.cache.bounds(header2), Some(&Rect { posx: 0.0, posy: 100.0, width: 400.0, height: 50.0 }));
+}
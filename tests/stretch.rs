@@ -0,0 +1,132 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn stretch_fills_row_exactly() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    let nodes: Vec<_> = (0..3)
+        .map(|_| {
+            let node = world.add(Some(root));
+            world.set_width(node, Units::Stretch(1.0));
+            world.set_height(node, Units::Pixels(50.0));
+            node
+        })
+        .collect();
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The leftover pixel goes to the first item.
+    assert_eq!(world.cache.bounds(nodes[0]), Some(&Rect { posx: 0.0, posy: 0.0, width: 34.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(nodes[1]), Some(&Rect { posx: 34.0, posy: 0.0, width: 33.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(nodes[2]), Some(&Rect { posx: 67.0, posy: 0.0, width: 33.0, height: 50.0 }));
+}
+
+#[test]
+fn stretch_leftover_goes_to_largest_remainder() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+
+    // Exact shares of 14.29, 28.57 and 57.14 pixels.
+    let nodes: Vec<_> = [1.0, 2.0, 4.0]
+        .into_iter()
+        .map(|factor| {
+            let node = world.add(Some(root));
+            world.set_width(node, Units::Pixels(50.0));
+            world.set_height(node, Units::Stretch(factor));
+            node
+        })
+        .collect();
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(nodes[0]), Some(&Rect { posx: 0.0, posy: 0.0, width: 50.0, height: 14.0 }));
+    assert_eq!(world.cache.bounds(nodes[1]), Some(&Rect { posx: 0.0, posy: 14.0, width: 50.0, height: 29.0 }));
+    assert_eq!(world.cache.bounds(nodes[2]), Some(&Rect { posx: 0.0, posy: 43.0, width: 50.0, height: 57.0 }));
+}
+
+#[test]
+fn stretch_space_and_size_fill_exactly() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Stretch(1.0));
+    world.set_height(node1, Units::Pixels(50.0));
+    world.set_right(node1, Units::Stretch(1.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Stretch(1.0));
+    world.set_height(node2, Units::Pixels(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // The space after the first node is collected before its size, so receives the leftover pixel.
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: 0.0, width: 33.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 67.0, posy: 0.0, width: 33.0, height: 50.0 }));
+}
+
+#[test]
+fn stretch_fills_wrap_line_exactly() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_layout_type(root, LayoutType::Row);
+    world.set_wrap(root, LayoutWrap::Wrap);
+    world.set_alignment(root, Alignment::TopLeft);
+
+    let nodes: Vec<_> = (0..3)
+        .map(|_| {
+            let node = world.add(Some(root));
+            world.set_width(node, Units::Stretch(1.0));
+            world.set_height(node, Units::Pixels(50.0));
+            node
+        })
+        .collect();
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(nodes[0]), Some(&Rect { posx: 0.0, posy: 0.0, width: 34.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(nodes[1]), Some(&Rect { posx: 34.0, posy: 0.0, width: 33.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(nodes[2]), Some(&Rect { posx: 67.0, posy: 0.0, width: 33.0, height: 50.0 }));
+}
+
+#[test]
+fn stretch_fills_grid_exactly() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(100.0));
+    world.set_height(root, Units::Pixels(100.0));
+    world.set_layout_type(root, LayoutType::Grid);
+    world.set_grid_columns(root, vec![Units::Stretch(1.0), Units::Stretch(1.0), Units::Stretch(1.0)]);
+    world.set_grid_rows(root, vec![Units::Stretch(1.0)]);
+
+    let nodes: Vec<_> = (0..3)
+        .map(|column| {
+            let node = world.add(Some(root));
+            world.set_column_start(node, column);
+            node
+        })
+        .collect();
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(nodes[0]), Some(&Rect { posx: 0.0, posy: 0.0, width: 34.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(nodes[1]), Some(&Rect { posx: 34.0, posy: 0.0, width: 33.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(nodes[2]), Some(&Rect { posx: 67.0, posy: 0.0, width: 33.0, height: 100.0 }));
+}
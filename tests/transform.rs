@@ -0,0 +1,130 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn transform_does_not_affect_layout() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(100.0));
+    world.set_transform(node1, Transform::scale(2.0, 2.0));
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(50.0));
+    world.set_height(node2, Units::Pixels(50.0));
+
+    let node3 = world.add(Some(root));
+    world.set_width(node3, Units::Pixels(100.0));
+    world.set_height(node3, Units::Pixels(100.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(node3), Some(&Rect { posx: 0.0, posy: 100.0, width: 100.0, height: 100.0 }));
+
+    // The scale is applied about the center of the node, and to its descendants.
+    assert_eq!(world.cache.window_bounds(node1), Some(&Rect { posx: -50.0, posy: -50.0, width: 200.0, height: 200.0 }));
+    assert_eq!(world.cache.window_bounds(node2), Some(&Rect { posx: -50.0, posy: -50.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.transform(node2), Some(Transform { a: 2.0, b: 0.0, c: 0.0, d: 2.0, e: -50.0, f: -50.0 }));
+    assert_eq!(world.cache.transform(node3), Some(Transform::translate(0.0, 100.0)));
+}
+
+#[test]
+fn transform_clip() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(200.0));
+    world.set_height(node1, Units::Pixels(200.0));
+    world.set_overflow(node1, Overflow::Hidden);
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(100.0));
+    world.set_transform(node2, Transform::translate(150.0, 0.0));
+
+    let node3 = world.add(Some(node2));
+    world.set_width(node3, Units::Pixels(100.0));
+    world.set_height(node3, Units::Pixels(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.clip(node2), Some(&Rect { posx: 150.0, posy: 0.0, width: 50.0, height: 100.0 }));
+    assert_eq!(world.cache.clip(node3), Some(&Rect { posx: 0.0, posy: 0.0, width: 50.0, height: 50.0 }));
+
+    // A node scaled down within its parent is clipped by the scaled clip of its parent.
+    world.set_transform(node2, Transform::scale(0.5, 0.5));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.clip(node2), Some(&Rect { posx: 25.0, posy: 25.0, width: 50.0, height: 50.0 }));
+    assert_eq!(world.cache.clip(node3), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 50.0 }));
+}
+
+#[test]
+fn transform_hit_test() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(200.0));
+    world.set_height(node, Units::Pixels(20.0));
+    world.set_position_type(node, PositionType::Absolute);
+    world.set_left(node, Units::Pixels(100.0));
+    world.set_top(node, Units::Pixels(100.0));
+    world.set_transform(node, Transform::rotate(std::f64::consts::FRAC_PI_2 as Scalar));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // Rotated about its center at (200, 110), the node spans from 190 to 210 horizontally and 10 to 210 vertically.
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 200.0, 30.0), Some(&node));
+    assert_eq!(hit_test(&root, &world.cache, &world.tree, &world.store, 120.0, 110.0), Some(&root));
+}
+
+#[test]
+fn transform_compose() {
+    let transform = Transform::scale(2.0, 4.0).then(&Transform::translate(10.0, 20.0));
+    assert_eq!(transform.apply(1.0, 1.0), (12.0, 24.0));
+
+    let inverse = transform.invert().unwrap();
+    assert_eq!(inverse.apply(12.0, 24.0), (1.0, 1.0));
+
+    assert_eq!(Transform::scale(0.0, 1.0).invert(), None);
+    assert_eq!(Transform::scale(2.0, 2.0).bounding_box(10.0, 10.0, 5.0, 5.0), (20.0, 20.0, 10.0, 10.0));
+}
+
+#[test]
+fn transform_zero_scale_clips_descendants() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_overflow(root, Overflow::Hidden);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(100.0));
+    world.set_transform(node1, Transform::scale(0.0, 0.0));
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(50.0));
+    world.set_height(node2, Units::Pixels(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    // Nothing under the zero scale is visible, rather than being unclipped.
+    assert_eq!(world.cache.clip(node2), Some(&Rect { posx: 0.0, posy: 0.0, width: 0.0, height: 0.0 }));
+}
@@ -0,0 +1,92 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn viewport_width_ignores_parent_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(800.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let container = world.add(Some(root));
+    world.set_width(container, Units::Pixels(200.0));
+    world.set_height(container, Units::Pixels(200.0));
+
+    let node = world.add(Some(container));
+    world.set_width(node, Units::ViewportWidth(80.0));
+    world.set_height(node, Units::ViewportHeight(50.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 640.0, height: 300.0 }));
+}
+
+#[test]
+fn viewport_min_max() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(800.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Row);
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::ViewportMin(10.0));
+    world.set_height(node, Units::ViewportMax(10.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node), Some(&Rect { posx: 0.0, posy: 0.0, width: 60.0, height: 80.0 }));
+}
+
+#[test]
+fn viewport_spacing_and_constraints() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(800.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_padding_left(root, Units::ViewportWidth(10.0));
+    world.set_vertical_gap(root, Units::ViewportHeight(5.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Stretch(1.0));
+    world.set_max_width(node1, Units::ViewportWidth(25.0));
+    world.set_height(node1, Units::Pixels(100.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(100.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 80.0, posy: 0.0, width: 200.0, height: 100.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 80.0, posy: 130.0, width: 100.0, height: 100.0 }));
+}
+
+#[test]
+fn viewport_units_in_overlay_and_grid() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(800.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Overlay);
+
+    let overlay_child = world.add(Some(root));
+    world.set_width(overlay_child, Units::ViewportWidth(50.0));
+    world.set_height(overlay_child, Units::ViewportHeight(50.0));
+    world.set_layout_type(overlay_child, LayoutType::Grid);
+    world.set_grid_columns(overlay_child, vec![Units::ViewportWidth(10.0), Units::Stretch(1.0)]);
+    world.set_grid_rows(overlay_child, vec![Units::ViewportHeight(10.0), Units::Stretch(1.0)]);
+
+    let grid_child = world.add(Some(overlay_child));
+    world.set_column_start(grid_child, 1);
+    world.set_row_start(grid_child, 1);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(overlay_child), Some(&Rect { posx: 0.0, posy: 0.0, width: 400.0, height: 300.0 }));
+    assert_eq!(world.cache.bounds(grid_child), Some(&Rect { posx: 80.0, posy: 60.0, width: 320.0, height: 240.0 }));
+}
@@ -0,0 +1,67 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+#[test]
+fn window_bounds_accumulate_positions() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(300.0));
+    world.set_height(node1, Units::Pixels(300.0));
+    world.set_left(node1, Units::Pixels(100.0));
+    world.set_top(node1, Units::Pixels(50.0));
+    world.set_vertical_scroll(node1, -30.0);
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(100.0));
+    world.set_left(node2, Units::Pixels(20.0));
+
+    let node3 = world.add(Some(node2));
+    world.set_width(node3, Units::Pixels(50.0));
+    world.set_height(node3, Units::Pixels(50.0));
+    world.set_position_type(node3, PositionType::Absolute);
+    world.set_right(node3, Units::Pixels(0.0));
+    world.set_bottom(node3, Units::Pixels(0.0));
+
+    world.cache.set_bounds(&root, 10.0, 20.0, 0.0, 0.0);
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 20.0, posy: -30.0, width: 100.0, height: 100.0 }));
+
+    assert_eq!(world.cache.window_bounds(root), Some(&Rect { posx: 10.0, posy: 20.0, width: 600.0, height: 600.0 }));
+    assert_eq!(world.cache.window_bounds(node1), Some(&Rect { posx: 110.0, posy: 70.0, width: 300.0, height: 300.0 }));
+    assert_eq!(world.cache.window_bounds(node2), Some(&Rect { posx: 130.0, posy: 40.0, width: 100.0, height: 100.0 }));
+    assert_eq!(world.cache.window_bounds(node3), Some(&Rect { posx: 180.0, posy: 90.0, width: 50.0, height: 50.0 }));
+}
+
+#[test]
+fn window_bounds_of_fixed_node() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(600.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(300.0));
+    world.set_height(node1, Units::Pixels(300.0));
+    world.set_left(node1, Units::Pixels(100.0));
+    world.set_vertical_scroll(node1, -30.0);
+
+    let node2 = world.add(Some(node1));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(100.0));
+    world.set_position_type(node2, PositionType::Fixed);
+    world.set_right(node2, Units::Pixels(0.0));
+    world.set_bottom(node2, Units::Pixels(0.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.window_bounds(node2), Some(&Rect { posx: 500.0, posy: 500.0, width: 100.0, height: 100.0 }));
+}
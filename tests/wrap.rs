@@ -428,3 +428,37 @@ fn wrap_row_auto_height_includes_lines_gap_and_padding() {
     assert_eq!(world.cache.bounds(b), Some(&Rect { posx: 100.0, posy: 5.0, width: 100.0, height: 50.0 }));
     assert_eq!(world.cache.bounds(c), Some(&Rect { posx: 0.0, posy: 65.0, width: 100.0, height: 50.0 }));
 }
+
+#[test]
+fn wrap_row_with_margins() {
+    // Spacing around items counts towards line breaks, and stretch spacing shares the free space of its line.
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(300.0));
+    world.set_height(root, Units::Pixels(300.0));
+    world.set_layout_type(root, LayoutType::Row);
+    world.set_wrap(root, LayoutWrap::Wrap);
+    world.set_alignment(root, Alignment::TopLeft);
+
+    let node1 = world.add(Some(root));
+    world.set_width(node1, Units::Pixels(100.0));
+    world.set_height(node1, Units::Pixels(50.0));
+    world.set_right(node1, Units::Pixels(20.0));
+
+    let node2 = world.add(Some(root));
+    world.set_width(node2, Units::Pixels(100.0));
+    world.set_height(node2, Units::Pixels(50.0));
+    world.set_top(node2, Units::Pixels(10.0));
+
+    let node3 = world.add(Some(root));
+    world.set_width(node3, Units::Pixels(100.0));
+    world.set_height(node3, Units::Pixels(50.0));
+    world.set_left(node3, Units::Stretch(1.0));
+
+    root.layout(&mut world.cache, &world.tree, &world.store, &mut ());
+
+    assert_eq!(world.cache.bounds(node1), Some(&Rect { posx: 0.0, posy: 0.0, width: 100.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(node2), Some(&Rect { posx: 120.0, posy: 10.0, width: 100.0, height: 50.0 }));
+    assert_eq!(world.cache.bounds(node3), Some(&Rect { posx: 200.0, posy: 60.0, width: 100.0, height: 50.0 }));
+}